@@ -1,3 +1,287 @@
-fn main() {
-    println!("Tataki CLI");
+use std::error::Error;
+use std::fs;
+use std::io::ErrorKind;
+use std::process::ExitCode;
+
+use chrono::Datelike;
+use chrono::Local;
+use chrono::SecondsFormat;
+use chrono::Weekday;
+use tt_lib::data::Document;
+use tt_lib::ics::Calendar;
+use tt_lib::planner::Planner;
+use tt_lib::simulator::Outcome;
+use tt_lib::simulator::Simulator;
+use tt_lib::types::Duration;
+use tt_lib::types::Severity;
+use tt_lib::types::experimental::plan::Plan;
+
+const USAGE: &str = "\
+Tataki CLI
+
+usage:
+  tt-cli plan <file> [<calendar.ics>...]
+  tt-cli explain <file> <blueprint> [<calendar.ics>...]
+  tt-cli check <file>
+  tt-cli simulate <file> [<weeks>] [--skip weekends|alternate-weekends]
+  tt-cli pause <file> <blueprint>
+  tt-cli resume <file> <blueprint>
+  tt-cli pin <file> <blueprint> <time>
+  tt-cli unpin <file> <blueprint>
+  tt-cli done <file> <blueprint>
+  tt-cli progress <file> <blueprint> <duration>
+  tt-cli postpone <file> <blueprint> [<duration>|until <time>]
+
+done, progress and postpone record what was just done in <file>.journal,
+where pin and unpin keep the pins, and which plan, explain and simulate
+read back. plan keeps the plan in <file>.plan, to start from there the
+next time.";
+
+/// How far ahead `simulate` may look, which keeps the end of the simulation
+/// within the range of timestamps.
+const MAX_WEEKS: u64 = 52 * 100;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args {
+        [command, path, calendars @ ..] if command == "plan" => plan(path, calendars),
+        [command, path, id, calendars @ ..] if command == "explain" => explain(path, id, calendars),
+        [command, path] if command == "check" => check(path),
+        [command, path] if command == "simulate" => simulate(path, "52", None),
+        [command, path, weeks] if command == "simulate" => simulate(path, weeks, None),
+        [command, path, flag, skip] if command == "simulate" && flag == "--skip" => {
+            simulate(path, "52", Some(skip))
+        }
+        [command, path, weeks, flag, skip] if command == "simulate" && flag == "--skip" => {
+            simulate(path, weeks, Some(skip))
+        }
+        [command, path, id] if command == "pause" => set_paused(path, id, true),
+        [command, path, id] if command == "resume" => set_paused(path, id, false),
+        [command, path, id, time] if command == "pin" => pin(path, id, time),
+        [command, path, id] if command == "unpin" => unpin(path, id),
+        [command, path, id] if command == "done" => record(path, id, "done"),
+        [command, path, id, duration] if command == "progress" => {
+            record(path, id, &format!("progress {duration}"))
+        }
+        [command, path, id] if command == "postpone" => record(path, id, "postpone backoff"),
+        [command, path, id, until @ ..] if command == "postpone" => {
+            record(path, id, &format!("postpone {}", until.join(" ")))
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+/// Prints the plan for the next week out of the data file at `path`,
+/// keeping off the busy time of the `calendars`, and keeps it in
+/// `<path>.plan` for the next one to start from.
+fn plan(path: &str, calendars: &[String]) -> Result<(), Box<dyn Error>> {
+    let plan = next_week(path, calendars, false)?;
+    print!("{}", plan.as_str());
+
+    let mut document = Document::default();
+    for entry in plan.entries() {
+        let planned_for = entry
+            .planned_for()
+            .to_rfc3339_opts(SecondsFormat::Secs, false);
+        let value = format!("{planned_for} {}", entry.duration());
+        document.add("plan", entry.blueprint_id(), &value);
+    }
+    fs::write(format!("{path}.plan"), document.to_string())?;
+    Ok(())
+}
+
+/// Prints why the blueprint `id` is planned the way it is in the plan for
+/// the next week.
+fn explain(path: &str, id: &str, calendars: &[String]) -> Result<(), Box<dyn Error>> {
+    let plan = next_week(path, calendars, true)?;
+    let explanation = plan
+        .explanation_for(id)
+        .ok_or_else(|| format!("{path}: no blueprint {id:?}"))?;
+    print!("{explanation}");
+    Ok(())
+}
+
+/// Plans the next week out of the data file at `path` and its journal,
+/// keeping its pins, the entries of the last plan that still hold, and off
+/// the busy time of the `calendars`.
+fn next_week(path: &str, calendars: &[String], explain: bool) -> Result<Plan, Box<dyn Error>> {
+    let book = load(path)?.book()?;
+    let history = load_next_to(path, "journal")?;
+    let journal = history.journal()?;
+    let pins = history.pins()?;
+    let previous = load_next_to(path, "plan")?.plan()?;
+
+    let start = Local::now();
+    let horizon = Duration::days(7);
+    let mut busy = Vec::new();
+    for calendar in calendars {
+        let calendar: Calendar = fs::read_to_string(calendar)
+            .map_err(|error| format!("{calendar}: {error}"))?
+            .parse()
+            .map_err(|error| format!("{calendar}: {error}"))?;
+        busy.extend(calendar.busy(start, start + horizon.timedelta()));
+    }
+
+    let planner = Planner::new(&book, &journal)
+        .with_previous(&previous)
+        .with_pins(&pins)
+        .with_busy(&busy);
+    let planner = if explain {
+        planner.with_explanations()
+    } else {
+        planner
+    };
+    let plan = planner.plan(start, horizon);
+    for stale_pin in plan.stale_pins() {
+        eprintln!("{path}: stale pin {stale_pin}");
+    }
+    if plan.moved() > 0 {
+        eprintln!("{path}: {} moved since the last plan", plan.moved());
+    }
+    Ok(plan)
+}
+
+/// Reads the data file at `path`, warning about the issues found in its
+/// book, which are not in the way of planning.
+fn load(path: &str) -> Result<Document, Box<dyn Error>> {
+    let document: Document = fs::read_to_string(path)?.parse()?;
+    for diagnostic in document.book()?.validate() {
+        eprintln!("{path}: {diagnostic}");
+    }
+    Ok(document)
+}
+
+/// Reads the file with the given `extension` kept next to the data file at
+/// `path`, such as its journal, which is empty until first written.
+fn load_next_to(path: &str, extension: &str) -> Result<Document, Box<dyn Error>> {
+    let path = format!("{path}.{extension}");
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(text.parse().map_err(|error| format!("{path}: {error}"))?),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(Document::default()),
+        Err(error) => Err(format!("{path}: {error}").into()),
+    }
+}
+
+/// Prints the issues found in the data file at `path`, and fails if any of
+/// them is an error.
+fn check(path: &str) -> Result<(), Box<dyn Error>> {
+    let book = fs::read_to_string(path)?.parse::<Document>()?.book()?;
+    let diagnostics = book.validate();
+    for diagnostic in &diagnostics {
+        println!("{diagnostic}");
+    }
+    let errors = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity() == Severity::Error)
+        .count();
+    if errors > 0 {
+        return Err(format!("{path}: {errors} error(s)").into());
+    }
+    Ok(())
+}
+
+/// Prints the load of each week, and the missed deadlines, over the next
+/// `weeks`, when every entry is completed as planned except those that
+/// `skip` names: `weekends`, or `alternate-weekends` starting with this one.
+fn simulate(path: &str, weeks: &str, skip: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let book = load(path)?.book()?;
+    let journal = load_next_to(path, "journal")?.journal()?;
+    let days = weeks
+        .parse::<u64>()
+        .ok()
+        .filter(|weeks| *weeks <= MAX_WEEKS)
+        .map(|weeks| weeks * 7)
+        .ok_or_else(|| format!("invalid weeks: {weeks:?}, at most {MAX_WEEKS}"))?;
+    let every = match skip {
+        None => None,
+        Some("weekends") => Some(1),
+        Some("alternate-weekends") => Some(2),
+        Some(skip) => return Err(format!("invalid skip: {skip:?}").into()),
+    };
+
+    let start = Local::now();
+    let monday = start.date_naive().week(Weekday::Mon).first_day();
+    let simulator = Simulator::new(&book, &journal);
+    let simulator = match every {
+        Some(every) => simulator.with_policy(move |entry| {
+            let date = entry.planned_for().date_naive();
+            let weekend = date.weekday().number_from_monday() > 5;
+            if weekend && (date - monday).num_weeks() % every == 0 {
+                Outcome::Skipped
+            } else {
+                Outcome::Completed
+            }
+        }),
+        None => simulator,
+    };
+    let simulation = simulator.simulate(start, Duration::days(days));
+    for week in simulation.weeks() {
+        println!("{week}");
+    }
+    for miss in simulation.misses() {
+        println!("missed {miss}");
+    }
+    Ok(())
+}
+
+/// Pauses or resumes the blueprint `id` in the data file at `path`.
+fn set_paused(path: &str, id: &str, paused: bool) -> Result<(), Box<dyn Error>> {
+    let mut document: Document = fs::read_to_string(path)?.parse()?;
+    if !document.set("blueprint", id, "paused", &paused.to_string()) {
+        return Err(format!("{path}: no blueprint {id:?}").into());
+    }
+    document.book()?;
+    fs::write(path, document.to_string())?;
+    Ok(())
+}
+
+/// Pins the blueprint `id` at `time` in the journal of the data file at
+/// `path`, so that it is planned then rather than where the planner would
+/// place it.
+fn pin(path: &str, id: &str, time: &str) -> Result<(), Box<dyn Error>> {
+    let book = fs::read_to_string(path)?.parse::<Document>()?.book()?;
+    if book.get(id).is_none() {
+        return Err(format!("{path}: no blueprint {id:?}").into());
+    }
+    let mut journal = load_next_to(path, "journal")?;
+    journal.add("pin", id, time);
+    journal.pins()?;
+    fs::write(format!("{path}.journal"), journal.to_string())?;
+    Ok(())
+}
+
+/// Removes the pins of the blueprint `id` from the journal of the data file
+/// at `path`.
+fn unpin(path: &str, id: &str) -> Result<(), Box<dyn Error>> {
+    let mut journal = load_next_to(path, "journal")?;
+    if !journal.remove("pin", id) {
+        return Err(format!("{path}: no pin for {id:?}").into());
+    }
+    fs::write(format!("{path}.journal"), journal.to_string())?;
+    Ok(())
+}
+
+/// Records in the journal of the data file at `path` that `action` was just
+/// taken on the blueprint `id`.
+fn record(path: &str, id: &str, action: &str) -> Result<(), Box<dyn Error>> {
+    let book = fs::read_to_string(path)?.parse::<Document>()?.book()?;
+    if book.get(id).is_none() {
+        return Err(format!("{path}: no blueprint {id:?}").into());
+    }
+    let mut journal = load_next_to(path, "journal")?;
+    let now = Local::now().to_rfc3339_opts(SecondsFormat::Secs, false);
+    journal.add("journal", id, &format!("{now} {action}"));
+    journal.journal()?;
+    fs::write(format!("{path}.journal"), journal.to_string())?;
+    Ok(())
 }
@@ -0,0 +1,859 @@
+use std::str::FromStr;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::TimeZone;
+
+use crate::types::Blueprint;
+use crate::types::DayOfWeek;
+use crate::types::Duration;
+use crate::types::ParseError;
+use crate::types::Priority;
+use crate::types::experimental::blackout::Blackout;
+use crate::types::experimental::book::Book;
+use crate::types::experimental::book::BookError;
+use crate::types::experimental::journal::Action;
+use crate::types::experimental::journal::Commit;
+use crate::types::experimental::journal::Journal;
+use crate::types::experimental::journal::Postpone;
+use crate::types::experimental::pins::Pin;
+use crate::types::experimental::pins::Pins;
+use crate::types::experimental::plan::Plan;
+use crate::types::experimental::plan_entry::PlanEntry;
+
+/// A data file, made of sections with `key = value` entries, that describes
+/// a [Book].
+///
+/// ```text
+/// # Chores around the house.
+/// [book]
+/// min_gap = 15min
+/// apart = gutters <-> ladder
+/// sat = 4h
+///
+/// [blueprint gutters]
+/// description = Clear the gutters
+/// duration = 2h
+/// recurrence = ^1y
+/// slot = Sat-Sun
+/// active_from = 2025-10-01
+/// active_until = 2025-12-01
+///
+/// [blackout vacation]
+/// from = 2025-08-01
+/// until = 2025-08-15
+/// ```
+///
+/// The same format keeps what happened to the book, in a file of its own:
+/// its journal, in `[journal]` sections read by
+/// [journal](Document::journal), and the pins set by hand next to it, in
+/// `[pin]` sections read by [pins](Document::pins). A [Plan] can be kept
+/// as well, in `[plan]` sections read by [plan](Document::plan), so that it
+/// can be planned again from there.
+///
+/// Blank lines and comments, starting with `#`, are kept as they are, so
+/// that the file can be edited and written back.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Document {
+    lines: Vec<Line>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Line {
+    /// A blank line, or a comment.
+    Verbatim(String),
+
+    /// The header of a section, e.g. `[blueprint gutters]`.
+    Section { kind: String, name: Option<String> },
+
+    /// An entry of the current section.
+    Entry { key: String, value: String },
+}
+
+/// The reasons why a [Document] cannot be read.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DataError {
+    /// The line is neither a section, an entry, a comment nor blank.
+    Syntax(usize),
+
+    /// The section or key on the line is not known.
+    Unexpected { line: usize, what: String },
+
+    /// The value on the line cannot be parsed.
+    Value { line: usize, error: ParseError },
+
+    /// The section lacks a required key.
+    Missing { section: String, key: &'static str },
+
+    /// The blueprints cannot make up a [Book].
+    Book(BookError),
+}
+
+impl std::fmt::Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataError::Syntax(line) => write!(f, "line {line}: invalid syntax"),
+            DataError::Unexpected { line, what } => write!(f, "line {line}: unexpected {what:?}"),
+            DataError::Value { line, error } => write!(f, "line {line}: {error}"),
+            DataError::Missing { section, key } => write!(f, "[{section}]: missing {key:?}"),
+            DataError::Book(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for DataError {}
+
+/// The entries of a section, along with their line numbers.
+struct Section<'a> {
+    line: usize,
+    kind: &'a str,
+    name: Option<&'a str>,
+    entries: Vec<(usize, &'a str, &'a str)>,
+}
+
+impl Section<'_> {
+    /// Returns the value of `key`, which is required.
+    fn require(&self, key: &'static str) -> Result<(usize, &str), DataError> {
+        self.get(key).ok_or_else(|| DataError::Missing {
+            section: match self.name {
+                Some(name) => format!("{} {name}", self.kind),
+                None => self.kind.to_string(),
+            },
+            key,
+        })
+    }
+
+    /// Returns the last value of `key`, along with its line number.
+    fn get(&self, key: &str) -> Option<(usize, &str)> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(_, k, _)| *k == key)
+            .map(|(line, _, value)| (*line, *value))
+    }
+}
+
+impl Document {
+    /// Reads the [Book] described by the document.
+    pub fn book(&self) -> Result<Book, DataError> {
+        let mut settings = Vec::new();
+        let mut blueprints = Vec::new();
+        let mut blackouts = Vec::new();
+        for section in self.sections() {
+            match (section.kind, section.name) {
+                ("book", None) => settings.extend(section.entries),
+                ("blueprint", Some(id)) => blueprints.push(blueprint(id, &section)?),
+                ("blackout", Some(name)) => blackouts.push(blackout(name, &section)?),
+                _ => {
+                    return Err(DataError::Unexpected {
+                        line: section.line,
+                        what: section.kind.to_string(),
+                    });
+                }
+            }
+        }
+
+        let mut book = Book::new(blueprints).map_err(DataError::Book)?;
+        let mut capacity = book.capacity();
+        for (line, key, value) in settings {
+            match key {
+                "min_gap" => book = book.with_min_gap(parse(line, value)?),
+                "pull_forward" => book = book.with_pull_forward(parse(line, value)?),
+                "leveling" => book = book.with_leveling(parse(line, value)?),
+                "apart" => book = book.with_anti_affinity(parse(line, value)?),
+                "daily" => capacity = capacity.with_daily(parse(line, value)?),
+                "weekly" => capacity = capacity.with_weekly(parse(line, value)?),
+                _ => {
+                    let day = weekday(key).ok_or_else(|| unexpected(line, key))?;
+                    capacity = capacity.with_weekday(day, parse(line, value)?);
+                }
+            }
+        }
+        Ok(blackouts
+            .into_iter()
+            .fold(book.with_capacity(capacity), Book::with_blackout))
+    }
+
+    /// Reads the [Pins] kept in the `[pin]` sections, where each entry pins
+    /// the blueprint it is named after, e.g. `gutters = 2025-10-25T10:00`.
+    pub fn pins(&self) -> Result<Pins, DataError> {
+        let mut pins = Vec::new();
+        for section in self.sections() {
+            if let ("pin", None) = (section.kind, section.name) {
+                for (line, key, value) in section.entries {
+                    pins.push(Pin::new(key.to_string(), timestamp(line, value)?));
+                }
+            }
+        }
+        Ok(Pins::new(pins))
+    }
+
+    /// Reads the [Journal] kept in the `[journal]` sections, where each entry
+    /// records a commit of the blueprint it is named after: when it was
+    /// committed, then `done`, `progress <duration>`, or `postpone` followed
+    /// by `backoff`, a duration or `until <timestamp>`.
+    ///
+    /// ```text
+    /// [journal]
+    /// gutters = 2025-10-25T12:00 progress 1h
+    /// ladder = 2025-10-25T14:00 postpone until 2025-11-01
+    /// gutters = 2025-10-26T12:00 done
+    /// ```
+    pub fn journal(&self) -> Result<Journal, DataError> {
+        let mut commits = Vec::new();
+        for section in self.sections() {
+            if let ("journal", None) = (section.kind, section.name) {
+                for (line, key, value) in section.entries {
+                    commits.push(commit(line, key, value)?);
+                }
+            }
+        }
+        commits.sort_by_key(Commit::committed_at);
+        Ok(Journal::new(commits))
+    }
+
+    /// Reads the [Plan] kept in the `[plan]` sections, where each entry plans
+    /// the blueprint it is named after for a time and a duration, e.g.
+    /// `gutters = 2025-10-25T10:00:00+02:00 2h`.
+    pub fn plan(&self) -> Result<Plan, DataError> {
+        let mut entries = Vec::new();
+        for section in self.sections() {
+            if let ("plan", None) = (section.kind, section.name) {
+                for (line, key, value) in section.entries {
+                    let (planned_for, duration) = value
+                        .split_once(' ')
+                        .ok_or_else(|| invalid(line, "plan entry", value))?;
+                    entries.push(PlanEntry::new(
+                        key.to_string(),
+                        parse(line, duration.trim())?,
+                        timestamp(line, planned_for)?,
+                    ));
+                }
+            }
+        }
+        entries.sort_by_key(PlanEntry::planned_for);
+        Ok(Plan::new(entries))
+    }
+
+    /// Adds an entry `key = value` to the section `[kind]`, adding the
+    /// section at the end if it is not there yet.
+    pub fn add(&mut self, kind: &str, key: &str, value: &str) {
+        let entry = Line::Entry {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        let Some(start) = self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line, Line::Section { kind: k, name: None } if k == kind))
+        else {
+            if !self.lines.is_empty() {
+                self.lines.push(Line::Verbatim(String::new()));
+            }
+            self.lines.push(Line::Section {
+                kind: kind.to_string(),
+                name: None,
+            });
+            self.lines.push(entry);
+            return;
+        };
+        let end = self.lines[start + 1..]
+            .iter()
+            .position(|line| matches!(line, Line::Section { .. }))
+            .map_or(self.lines.len(), |idx| start + 1 + idx);
+
+        let at = (start + 1..end)
+            .rev()
+            .find(|idx| matches!(self.lines[*idx], Line::Entry { .. }))
+            .map_or(start + 1, |idx| idx + 1);
+        self.lines.insert(at, entry);
+    }
+
+    /// Removes the entries of `key` from the sections `[kind]`.
+    ///
+    /// Returns false if there is no such entry.
+    pub fn remove(&mut self, kind: &str, key: &str) -> bool {
+        let len = self.lines.len();
+        let mut in_section = false;
+        self.lines.retain(|line| match line {
+            Line::Section { kind: k, name } => {
+                in_section = k == kind && name.is_none();
+                true
+            }
+            Line::Entry { key: k, .. } => !in_section || k != key,
+            Line::Verbatim(_) => true,
+        });
+        self.lines.len() < len
+    }
+
+    /// Sets `key` to `value` in the section `[kind name]`, adding the entry
+    /// if it is not there yet.
+    ///
+    /// Returns false if there is no such section.
+    pub fn set(&mut self, kind: &str, name: &str, key: &str, value: &str) -> bool {
+        let Some(start) = self.lines.iter().position(|line| {
+            matches!(line, Line::Section { kind: k, name: Some(n) } if k == kind && n == name)
+        }) else {
+            return false;
+        };
+        let end = self.lines[start + 1..]
+            .iter()
+            .position(|line| matches!(line, Line::Section { .. }))
+            .map_or(self.lines.len(), |idx| start + 1 + idx);
+
+        let entries = start + 1..end;
+        let value = value.to_string();
+        for line in &mut self.lines[entries.clone()] {
+            if let Line::Entry { key: k, value: v } = line
+                && k == key
+            {
+                *v = value;
+                return true;
+            }
+        }
+        let at = entries
+            .rev()
+            .find(|idx| matches!(self.lines[*idx], Line::Entry { .. }))
+            .map_or(start + 1, |idx| idx + 1);
+        let key = key.to_string();
+        self.lines.insert(at, Line::Entry { key, value });
+        true
+    }
+
+    fn sections(&self) -> Vec<Section<'_>> {
+        let mut sections: Vec<Section> = Vec::new();
+        for (idx, line) in self.lines.iter().enumerate() {
+            match line {
+                Line::Verbatim(_) => {}
+                Line::Section { kind, name } => sections.push(Section {
+                    line: idx + 1,
+                    kind,
+                    name: name.as_deref(),
+                    entries: Vec::new(),
+                }),
+                Line::Entry { key, value } => {
+                    if let Some(section) = sections.last_mut() {
+                        section.entries.push((idx + 1, key, value));
+                    }
+                }
+            }
+        }
+        sections
+    }
+}
+
+impl FromStr for Document {
+    type Err = DataError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Vec::new();
+        let mut in_section = false;
+        for (idx, raw) in s.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                lines.push(Line::Verbatim(raw.to_string()));
+            } else if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let (kind, name) = match header.trim().split_once(' ') {
+                    Some((kind, name)) => (kind, Some(name.trim().to_string())),
+                    None => (header.trim(), None),
+                };
+                lines.push(Line::Section {
+                    kind: kind.to_string(),
+                    name,
+                });
+                in_section = true;
+            } else if let Some((key, value)) = line.split_once('=')
+                && !key.trim().is_empty()
+                && in_section
+            {
+                lines.push(Line::Entry {
+                    key: key.trim().to_string(),
+                    value: value.trim().to_string(),
+                });
+            } else {
+                return Err(DataError::Syntax(idx + 1));
+            }
+        }
+        Ok(Self { lines })
+    }
+}
+
+impl std::fmt::Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for line in &self.lines {
+            match line {
+                Line::Verbatim(line) => writeln!(f, "{line}")?,
+                Line::Section { kind, name: None } => writeln!(f, "[{kind}]")?,
+                Line::Section {
+                    kind,
+                    name: Some(name),
+                } => writeln!(f, "[{kind} {name}]")?,
+                Line::Entry { key, value } => writeln!(f, "{key} = {value}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the blueprint `id` out of its `section`.
+fn blueprint(id: &str, section: &Section) -> Result<Blueprint, DataError> {
+    let (line, duration) = section.require("duration")?;
+    let duration = parse(line, duration)?;
+    let (line, recurrence) = section.require("recurrence")?;
+    let recurrence = parse(line, recurrence)?;
+    let (line, slot) = section.require("slot")?;
+    let slot = parse(line, slot)?;
+    let priority = match section.get("priority") {
+        Some((line, priority)) => parse(line, priority)?,
+        None => Priority::Norm,
+    };
+    let description = section.get("description").map_or(id, |(_, d)| d);
+
+    let mut blueprint = Blueprint::new(
+        id.to_string(),
+        description.to_string(),
+        duration,
+        priority,
+        recurrence,
+        slot,
+    );
+    for &(line, key, value) in &section.entries {
+        blueprint = match key {
+            "description" | "duration" | "recurrence" | "slot" | "priority" => blueprint,
+            "deadline" => blueprint.with_deadline(parse(line, value)?),
+            "expires_at" => blueprint.with_expiry(timestamp(line, value)?),
+            "catch_up" => blueprint.with_catch_up(parse(line, value)?),
+            "split" => blueprint.with_split(parse(line, value)?),
+            "buffers" => {
+                let (before, after) = value
+                    .split_once(',')
+                    .ok_or_else(|| invalid(line, "buffers", value))?;
+                blueprint.with_buffers(
+                    parse::<Duration>(line, before.trim())?,
+                    parse::<Duration>(line, after.trim())?,
+                )
+            }
+            "dependency" => blueprint.with_dependency(parse(line, value)?),
+            "group" => blueprint.with_group(value.to_string()),
+            "active_from" => blueprint.with_active_from(timestamp(line, value)?),
+            "active_until" => blueprint.with_active_until(timestamp(line, value)?),
+            "paused" => blueprint.with_paused(flag(line, value)?),
+            "travel_safe" => blueprint.with_travel_safe(flag(line, value)?),
+            _ => return Err(unexpected(line, key)),
+        };
+    }
+    Ok(blueprint)
+}
+
+/// Reads the blackout `name` out of its `section`.
+fn blackout(name: &str, section: &Section) -> Result<Blackout, DataError> {
+    let (line, from) = section.require("from")?;
+    let from = timestamp(line, from)?;
+    let (line, until) = section.require("until")?;
+    let until = timestamp(line, until)?;
+
+    let mut blackout = Blackout::new(name.to_string(), from, until);
+    for &(line, key, value) in &section.entries {
+        match key {
+            "from" | "until" => {}
+            "travel_safe" => {
+                if flag(line, value)? {
+                    blackout = blackout.with_travel_safe();
+                }
+            }
+            _ => return Err(unexpected(line, key)),
+        }
+    }
+    Ok(blackout)
+}
+
+/// Reads a commit of the blueprint `id` out of the `value` of its journal
+/// entry.
+fn commit(line: usize, id: &str, value: &str) -> Result<Commit, DataError> {
+    let words: Vec<_> = value.split_whitespace().collect();
+    let [committed_at, action @ ..] = words.as_slice() else {
+        return Err(invalid(line, "commit", value));
+    };
+    let committed_at = timestamp(line, committed_at)?;
+    let action = match action {
+        ["done"] => Action::Completed,
+        ["progress", duration] => Action::Progressed(parse(line, duration)?),
+        ["postpone", "backoff"] => Action::Postponed(Postpone::Backoff),
+        ["postpone", "until", ts] => Action::Postponed(Postpone::Until(timestamp(line, ts)?)),
+        ["postpone", duration] => Action::Postponed(Postpone::By(parse(line, duration)?)),
+        _ => return Err(invalid(line, "commit", value)),
+    };
+    Ok(Commit::new(id.to_string(), committed_at, action))
+}
+
+fn parse<T: FromStr<Err = ParseError>>(line: usize, value: &str) -> Result<T, DataError> {
+    value
+        .parse()
+        .map_err(|error| DataError::Value { line, error })
+}
+
+/// Parses either an RFC 3339 timestamp, a local date and time, e.g.
+/// `2025-10-25T10:00`, or a date, which stands for its local midnight.
+fn timestamp(line: usize, value: &str) -> Result<DateTime<Local>, DataError> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Local));
+    }
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })
+        .and_then(|ts| Local.from_local_datetime(&ts).earliest())
+        .ok_or_else(|| invalid(line, "timestamp", value))
+}
+
+/// Parses the name of a day of the week in lowercase, e.g. `sat`.
+fn weekday(key: &str) -> Option<DayOfWeek> {
+    let mut chars = key.chars();
+    let first = chars.next()?.to_ascii_uppercase();
+    format!("{first}{}", chars.as_str()).parse().ok()
+}
+
+fn flag(line: usize, value: &str) -> Result<bool, DataError> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(invalid(line, "flag", value)),
+    }
+}
+
+fn invalid(line: usize, expected: &'static str, value: &str) -> DataError {
+    DataError::Value {
+        line,
+        error: ParseError::new(expected, value),
+    }
+}
+
+fn unexpected(line: usize, what: &str) -> DataError {
+    DataError::Unexpected {
+        line,
+        what: what.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test::d;
+    use crate::types::Dependency;
+    use crate::types::Recurrence;
+    use crate::types::Slot;
+    use crate::types::TimeUnit;
+    use crate::types::WeekSlot;
+    use crate::types::experimental::anti_affinity::AntiAffinity;
+    use crate::types::experimental::anti_affinity::Separation;
+    use crate::types::experimental::anti_affinity::Target;
+    use crate::types::experimental::capacity::Capacity;
+
+    const CHORES: &str = "\
+# Chores around the house.
+[book]
+min_gap = 15min
+leveling = 14d
+apart = gutters <-> ladder 2d ~1d
+daily = 2h
+sat = 4h
+
+[blueprint gutters]
+description = Clear the gutters
+duration = 2h
+recurrence = ^1y
+slot = Sat-Sun
+active_from = 2025-10-01
+active_until = 2025-12-01
+
+[blueprint ladder]
+description = Fix the ladder
+duration = 1h
+priority = HIGH
+recurrence = ^1
+slot = Sat-Sun
+travel_safe = true
+
+[blackout vacation]
+from = 2025-08-01
+until = 2025-08-15
+travel_safe = true
+";
+
+    #[test]
+    fn test_book() {
+        let sut: Document = CHORES.parse().unwrap();
+        let book = sut.book().unwrap();
+
+        assert_eq!(Some(Duration::minutes(15)), book.min_gap());
+        assert_eq!(Some(Duration::days(14)), book.leveling());
+        assert_eq!(
+            Capacity::unlimited()
+                .with_daily(Duration::hours(2))
+                .with_weekday(DayOfWeek::Sat, Duration::hours(4)),
+            book.capacity()
+        );
+        assert_eq!(
+            &[AntiAffinity::new(
+                Target::Blueprint("gutters".to_string()),
+                Target::Blueprint("ladder".to_string()),
+                Separation::AtLeast(Duration::days(2))
+            )
+            .with_penalty(Duration::days(1))],
+            book.anti_affinities()
+        );
+        assert_eq!(
+            Some(
+                &Blueprint::new(
+                    "gutters".to_string(),
+                    "Clear the gutters".to_string(),
+                    Duration::hours(2),
+                    Priority::Norm,
+                    Recurrence::Period {
+                        spacing: Duration::of(1, TimeUnit::Year),
+                    },
+                    Slot::Week(WeekSlot::weekend()),
+                )
+                .with_active_from(d(2025, 10, 1, 0, 0, 0))
+                .with_active_until(d(2025, 12, 1, 0, 0, 0))
+            ),
+            book.get("gutters")
+        );
+        assert_eq!(Priority::High, book.get("ladder").unwrap().priority());
+        assert!(book.get("ladder").unwrap().is_travel_safe());
+        assert_eq!(
+            &[Blackout::new(
+                "vacation".to_string(),
+                d(2025, 8, 1, 0, 0, 0),
+                d(2025, 8, 15, 0, 0, 0)
+            )
+            .with_travel_safe()],
+            book.blackouts()
+        );
+    }
+
+    #[test]
+    fn test_set() {
+        let mut sut: Document = CHORES.parse().unwrap();
+
+        assert!(sut.set("blueprint", "gutters", "paused", "true"));
+        assert!(sut.set("blueprint", "ladder", "dependency", "after gutters"));
+        assert!(!sut.set("blueprint", "roof", "paused", "true"));
+
+        let book = sut.book().unwrap();
+        assert!(book.get("gutters").unwrap().is_paused());
+        assert_eq!(
+            &[Dependency::new("gutters".to_string())],
+            book.get("ladder").unwrap().dependencies()
+        );
+
+        assert!(sut.set("blueprint", "gutters", "paused", "false"));
+        let text = sut.to_string();
+        assert!(text.starts_with("# Chores around the house.\n"));
+        assert!(text.contains("active_until = 2025-12-01\npaused = false\n\n[blueprint ladder]"));
+        assert_eq!(Ok(sut), text.parse());
+    }
+
+    #[test]
+    fn test_pins() {
+        let mut sut = Document::default();
+        assert_eq!(Ok(Pins::new(vec![])), sut.pins());
+
+        sut.add("journal", "ladder", "2025-10-18T12:00 done");
+        sut.add("pin", "ladder", "2025-10-25T10:00");
+        sut.add("pin", "gutters", "2025-10-26T09:00:00+00:00");
+        sut.add("pin", "ladder", "2025-10-18T10:00");
+        assert_eq!(
+            Ok(Pins::new(vec![
+                Pin::new("ladder".to_string(), d(2025, 10, 25, 10, 0, 0)),
+                Pin::new(
+                    "gutters".to_string(),
+                    DateTime::parse_from_rfc3339("2025-10-26T09:00:00+00:00")
+                        .unwrap()
+                        .with_timezone(&Local)
+                ),
+                Pin::new("ladder".to_string(), d(2025, 10, 18, 10, 0, 0)),
+            ])),
+            sut.pins()
+        );
+        assert_eq!(1, sut.journal().unwrap().commits().len());
+
+        let text = sut.to_string();
+        assert_eq!(
+            "[journal]\nladder = 2025-10-18T12:00 done\n\n[pin]\nladder = \
+             2025-10-25T10:00\ngutters = 2025-10-26T09:00:00+00:00\nladder = 2025-10-18T10:00\n",
+            text
+        );
+        assert_eq!(Ok(sut.clone()), text.parse());
+
+        assert!(sut.remove("pin", "ladder"));
+        assert!(!sut.remove("pin", "ladder"));
+        assert!(!sut.remove("journal", "gutters"));
+        assert_eq!(1, sut.pins().unwrap().pins().len());
+
+        sut.add("pin", "ladder", "soon");
+        assert_eq!(
+            Err(DataError::Value {
+                line: 6,
+                error: ParseError::new("timestamp", "soon")
+            }),
+            sut.pins()
+        );
+    }
+
+    #[test]
+    fn test_journal() {
+        let mut sut = Document::default();
+        assert_eq!(Ok(Journal::new(vec![])), sut.journal());
+
+        sut.add("journal", "gutters", "2025-10-25T12:00 progress 1h");
+        sut.add(
+            "journal",
+            "ladder",
+            "2025-10-25T14:00 postpone until 2025-11-01",
+        );
+        sut.add("journal", "ladder", "2025-10-25T10:00 postpone backoff");
+        sut.add("journal", "ladder", "2025-10-26T10:00 postpone 2d");
+        sut.add("journal", "gutters", "2025-10-26T12:00 done");
+        assert_eq!(
+            Ok(Journal::new(vec![
+                Commit::postponed(
+                    "ladder".into(),
+                    d(2025, 10, 25, 10, 0, 0),
+                    Postpone::Backoff
+                ),
+                Commit::progressed(
+                    "gutters".into(),
+                    d(2025, 10, 25, 12, 0, 0),
+                    Duration::hours(1)
+                ),
+                Commit::postponed(
+                    "ladder".into(),
+                    d(2025, 10, 25, 14, 0, 0),
+                    Postpone::Until(d(2025, 11, 1, 0, 0, 0))
+                ),
+                Commit::postponed(
+                    "ladder".into(),
+                    d(2025, 10, 26, 10, 0, 0),
+                    Postpone::By(Duration::days(2))
+                ),
+                Commit::completed("gutters".into(), d(2025, 10, 26, 12, 0, 0)),
+            ])),
+            sut.journal()
+        );
+        assert!(
+            sut.to_string()
+                .starts_with("[journal]\ngutters = 2025-10-25T12:00 progress 1h\n")
+        );
+        assert_eq!(Ok(sut.clone()), sut.to_string().parse());
+
+        sut.add("journal", "ladder", "2025-10-27T10:00 skipped");
+        assert_eq!(
+            Err(DataError::Value {
+                line: 7,
+                error: ParseError::new("commit", "2025-10-27T10:00 skipped")
+            }),
+            sut.journal()
+        );
+    }
+
+    #[test]
+    fn test_plan() {
+        let mut sut = Document::default();
+        assert_eq!(Ok(Plan::new(vec![])), sut.plan());
+
+        sut.add("plan", "ladder", "2025-10-25T10:00 1h");
+        sut.add("plan", "gutters", "2025-10-18T10:00 90min");
+        assert_eq!(
+            Ok(Plan::new(vec![
+                PlanEntry::new(
+                    "gutters".into(),
+                    Duration::minutes(90),
+                    d(2025, 10, 18, 10, 0, 0)
+                ),
+                PlanEntry::new(
+                    "ladder".into(),
+                    Duration::hours(1),
+                    d(2025, 10, 25, 10, 0, 0)
+                ),
+            ])),
+            sut.plan()
+        );
+
+        sut.add("plan", "ladder", "2025-11-01T10:00");
+        assert_eq!(
+            Err(DataError::Value {
+                line: 4,
+                error: ParseError::new("plan entry", "2025-11-01T10:00")
+            }),
+            sut.plan()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(DataError::Syntax(1)),
+            "duration = 1h".parse::<Document>()
+        );
+        assert_eq!(
+            Err(DataError::Syntax(2)),
+            "[book]\nmin_gap".parse::<Document>()
+        );
+
+        let sut: Document = "[blueprint a]\nduration = 1h\nslot = Mon".parse().unwrap();
+        assert_eq!(
+            Err(DataError::Missing {
+                section: "blueprint a".to_string(),
+                key: "recurrence"
+            }),
+            sut.book()
+        );
+
+        let sut: Document = "[blueprint a]\nduration = 1h\nrecurrence = ^1\nslot = Moon"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            Err(DataError::Value {
+                line: 4,
+                error: ParseError::new("slot", "Moon")
+            }),
+            sut.book()
+        );
+
+        let sut: Document = "[pin]\nladder = 2025-10-25T10:00".parse().unwrap();
+        assert_eq!(
+            Err(DataError::Unexpected {
+                line: 1,
+                what: "pin".to_string()
+            }),
+            sut.book()
+        );
+
+        let sut: Document = "[book]\nsaturday = 4h".parse().unwrap();
+        assert_eq!(
+            Err(DataError::Unexpected {
+                line: 2,
+                what: "saturday".to_string()
+            }),
+            sut.book()
+        );
+
+        let sut: Document = "[shelf]\nduration = 1h".parse().unwrap();
+        assert_eq!(
+            Err(DataError::Unexpected {
+                line: 1,
+                what: "shelf".to_string()
+            }),
+            sut.book()
+        );
+    }
+}
@@ -0,0 +1,675 @@
+use std::str::FromStr;
+
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::TimeDelta;
+use chrono::TimeZone;
+use chrono::Utc;
+
+use crate::types::DayOfWeek;
+use crate::types::experimental::busy::Busy;
+
+/// The events of an iCalendar file (RFC 5545), which take busy time.
+///
+/// Only what tells when events take place is read: their start, their end
+/// or duration, and their recurrence rule along with its exceptions.
+///
+/// There is no time zone database around, so times with a `TZID` are only
+/// read when it names the local time zone, as set by `TZ` or else by the
+/// `/etc/localtime` link. Calendars with times in any other zone are
+/// rejected rather than misread.
+///
+/// Events that are cancelled, or transparent, do not take busy time and are
+/// left out.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Calendar {
+    events: Vec<Event>,
+}
+
+/// An event, with its times in local time.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Event {
+    uid: Option<String>,
+    summary: String,
+    start: NaiveDateTime,
+    length: TimeDelta,
+    rule: Option<Rule>,
+    exdates: Vec<NaiveDateTime>,
+    recurrence_id: Option<NaiveDateTime>,
+}
+
+/// The recurrence rule of an event, e.g. `FREQ=WEEKLY;BYDAY=MO,WE`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Rule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<usize>,
+    until: Option<NaiveDateTime>,
+    by_day: Vec<DayOfWeek>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The reasons why a [Calendar] cannot be read.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IcsError {
+    /// The line is not a property, or components are not nested properly.
+    Syntax(usize),
+
+    /// The property on the line cannot be read, or is not supported.
+    Property { line: usize, name: String },
+
+    /// The event that begins on the line lacks a required property.
+    Missing { line: usize, name: &'static str },
+
+    /// The time on the line is in a time zone that is not known to be the
+    /// local one.
+    TimeZone { line: usize, tzid: String },
+}
+
+impl std::fmt::Display for IcsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IcsError::Syntax(line) => write!(f, "line {line}: invalid syntax"),
+            IcsError::Property { line, name } => write!(f, "line {line}: unsupported {name}"),
+            IcsError::Missing { line, name } => write!(f, "line {line}: event lacks {name}"),
+            IcsError::TimeZone { line, tzid } => {
+                write!(
+                    f,
+                    "line {line}: time zone {tzid} is not known to be the local one"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for IcsError {}
+
+impl Calendar {
+    /// Returns the busy time taken by the occurrences of the events that
+    /// overlap `[start, end)`, ordered by time.
+    pub fn busy(&self, start: DateTime<Local>, end: DateTime<Local>) -> Vec<Busy> {
+        let mut busy: Vec<_> = self
+            .events
+            .iter()
+            .flat_map(|event| {
+                event
+                    .occurrences(end.naive_local())
+                    .into_iter()
+                    .filter(|ts| !event.exdates.contains(ts) && !self.is_overridden(event, *ts))
+                    .filter_map(|ts| {
+                        let from = local(ts)?;
+                        let until = local(ts + event.length)?;
+                        (from < end && start < until)
+                            .then(|| Busy::new(event.summary.clone(), from, until))
+                    })
+            })
+            .collect();
+        busy.sort_by_key(Busy::start);
+        busy
+    }
+
+    /// Returns true if the occurrence of the recurring `event` at `ts` was
+    /// replaced by another event.
+    fn is_overridden(&self, event: &Event, ts: NaiveDateTime) -> bool {
+        event.rule.is_some()
+            && event.uid.is_some()
+            && self
+                .events
+                .iter()
+                .any(|other| other.uid == event.uid && other.recurrence_id == Some(ts))
+    }
+}
+
+impl Event {
+    /// Returns the start of the occurrences of the event, in order, up to
+    /// `end`.
+    fn occurrences(&self, end: NaiveDateTime) -> Vec<NaiveDateTime> {
+        let Some(rule) = &self.rule else {
+            return vec![self.start];
+        };
+
+        let mut occurrences = Vec::new();
+        let mut count = 0;
+        for period in 0.. {
+            let (opens_at, candidates) = rule.period(self.start, period);
+            if opens_at >= end || rule.until.is_some_and(|until| opens_at > until) {
+                break;
+            }
+            for ts in candidates.into_iter().filter(|ts| *ts >= self.start) {
+                count += 1;
+                if rule.count.is_some_and(|limit| count > limit)
+                    || rule.until.is_some_and(|until| ts > until)
+                {
+                    return occurrences;
+                }
+                if ts < end {
+                    occurrences.push(ts);
+                }
+            }
+        }
+        occurrences
+    }
+}
+
+impl Rule {
+    /// Returns when the `period`-th period of the rule opens, counting from
+    /// `start`, along with the occurrences within it.
+    fn period(&self, start: NaiveDateTime, period: u32) -> (NaiveDateTime, Vec<NaiveDateTime>) {
+        let steps = period * self.interval;
+        let date = start.date();
+        match self.frequency {
+            Frequency::Daily => {
+                let ts = start + TimeDelta::days(steps as i64);
+                let matches = self.by_day.is_empty() || self.by_day.contains(&ts.weekday().into());
+                (ts, if matches { vec![ts] } else { vec![] })
+            }
+            Frequency::Weekly if self.by_day.is_empty() => {
+                let ts = start + TimeDelta::weeks(steps as i64);
+                (ts, vec![ts])
+            }
+            Frequency::Weekly => {
+                let monday = date - TimeDelta::days(date.weekday().num_days_from_monday() as i64)
+                    + TimeDelta::weeks(steps as i64);
+                let mut days: Vec<_> = self.by_day.iter().map(|day| *day as i64).collect();
+                days.sort();
+                let occurrences = days
+                    .into_iter()
+                    .map(|day| (monday + TimeDelta::days(day)).and_time(start.time()))
+                    .collect();
+                (monday.and_time(start.time()), occurrences)
+            }
+            Frequency::Monthly => {
+                let months = date.month0() + steps;
+                let year = date.year() + (months / 12) as i32;
+                let month = months % 12 + 1;
+                let opens_at = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(date);
+                let ts = NaiveDate::from_ymd_opt(year, month, date.day());
+                (
+                    opens_at.and_time(start.time()),
+                    ts.map(|ts| ts.and_time(start.time())).into_iter().collect(),
+                )
+            }
+            Frequency::Yearly => {
+                let year = date.year() + steps as i32;
+                let opens_at = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or(date);
+                let ts = NaiveDate::from_ymd_opt(year, date.month(), date.day());
+                (
+                    opens_at.and_time(start.time()),
+                    ts.map(|ts| ts.and_time(start.time())).into_iter().collect(),
+                )
+            }
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut frequency = None;
+        let mut rule = Rule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+        };
+        for part in s.split(';') {
+            let (name, value) = part.split_once('=').ok_or(())?;
+            match name {
+                "FREQ" => {
+                    frequency = Some(match value {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(()),
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().map_err(|_| ())?,
+                "COUNT" => rule.count = Some(value.parse().map_err(|_| ())?),
+                "UNTIL" => rule.until = Some(datetime(value).ok_or(())?.0),
+                "BYDAY" => {
+                    rule.by_day = value
+                        .split(',')
+                        .map(|day| match day {
+                            "MO" => Ok(DayOfWeek::Mon),
+                            "TU" => Ok(DayOfWeek::Tue),
+                            "WE" => Ok(DayOfWeek::Wed),
+                            "TH" => Ok(DayOfWeek::Thu),
+                            "FR" => Ok(DayOfWeek::Fri),
+                            "SA" => Ok(DayOfWeek::Sat),
+                            "SU" => Ok(DayOfWeek::Sun),
+                            _ => Err(()),
+                        })
+                        .collect::<Result<_, _>>()?
+                }
+                "WKST" => {}
+                _ => return Err(()),
+            }
+        }
+        rule.frequency = frequency.ok_or(())?;
+        let by_day_supported = matches!(rule.frequency, Frequency::Daily | Frequency::Weekly);
+        if rule.interval == 0 || (!rule.by_day.is_empty() && !by_day_supported) {
+            return Err(());
+        }
+        Ok(rule)
+    }
+}
+
+impl FromStr for Calendar {
+    type Err = IcsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, local_zone().as_deref())
+    }
+}
+
+impl Calendar {
+    /// Reads a calendar, taking the times with a `TZID` of `zone` as local
+    /// time.
+    fn parse(s: &str, zone: Option<&str>) -> Result<Self, IcsError> {
+        // Long lines are folded into several, each following one starting
+        // with a space or a tab.
+        let mut lines: Vec<(usize, String)> = Vec::new();
+        for (idx, line) in s.lines().enumerate() {
+            if let Some(rest) = line.strip_prefix([' ', '\t'])
+                && let Some((_, last)) = lines.last_mut()
+            {
+                last.push_str(rest);
+            } else if !line.trim().is_empty() {
+                lines.push((idx + 1, line.to_string()));
+            }
+        }
+
+        let mut components: Vec<&str> = Vec::new();
+        let mut properties: Vec<(usize, &str, &str)> = Vec::new();
+        let mut begins_at = 0;
+        let mut events = Vec::new();
+        for (line, raw) in &lines {
+            let (name, tzid, value) = property(raw).ok_or(IcsError::Syntax(*line))?;
+            match name {
+                "BEGIN" => {
+                    if value == "VEVENT" {
+                        properties.clear();
+                        begins_at = *line;
+                    }
+                    components.push(value);
+                }
+                "END" => {
+                    if components.pop() != Some(value) {
+                        return Err(IcsError::Syntax(*line));
+                    }
+                    if value == "VEVENT"
+                        && let Some(event) = event(begins_at, &properties)?
+                    {
+                        events.push(event);
+                    }
+                }
+                _ if components.last() == Some(&"VEVENT") => {
+                    if let Some(tzid) = tzid
+                        && Some(tzid) != zone
+                    {
+                        return Err(IcsError::TimeZone {
+                            line: *line,
+                            tzid: tzid.to_string(),
+                        });
+                    }
+                    properties.push((*line, name, value));
+                }
+                _ => {}
+            }
+        }
+        if !components.is_empty() {
+            return Err(IcsError::Syntax(lines.last().map_or(0, |(line, _)| *line)));
+        }
+        Ok(Self { events })
+    }
+}
+
+/// Reads the event that begins on `line` out of its `properties`, unless it
+/// does not take busy time.
+fn event(line: usize, properties: &[(usize, &str, &str)]) -> Result<Option<Event>, IcsError> {
+    let get = |name: &str| properties.iter().find(|(_, n, _)| *n == name);
+    let unsupported = |line: usize, name: &str| IcsError::Property {
+        line,
+        name: name.to_string(),
+    };
+
+    if get("STATUS").is_some_and(|(_, _, value)| *value == "CANCELLED")
+        || get("TRANSP").is_some_and(|(_, _, value)| *value == "TRANSPARENT")
+    {
+        return Ok(None);
+    }
+
+    let &(at, name, value) = get("DTSTART").ok_or(IcsError::Missing {
+        line,
+        name: "DTSTART",
+    })?;
+    let (start, all_day) = datetime(value).ok_or_else(|| unsupported(at, name))?;
+    let length = match (get("DTEND"), get("DURATION")) {
+        (Some(&(at, name, value)), _) => {
+            datetime(value).ok_or_else(|| unsupported(at, name))?.0 - start
+        }
+        (None, Some(&(at, name, value))) => duration(value).ok_or_else(|| unsupported(at, name))?,
+        (None, None) if all_day => TimeDelta::days(1),
+        (None, None) => TimeDelta::zero(),
+    };
+
+    let mut event = Event {
+        uid: get("UID").map(|(_, _, value)| value.to_string()),
+        summary: get("SUMMARY").map_or_else(String::new, |(_, _, value)| unescape(value)),
+        start,
+        length,
+        rule: None,
+        exdates: Vec::new(),
+        recurrence_id: None,
+    };
+    for &(at, name, value) in properties {
+        match name {
+            "RRULE" => event.rule = Some(value.parse().map_err(|_| unsupported(at, name))?),
+            "EXDATE" => {
+                for exdate in value.split(',') {
+                    let (exdate, _) = datetime(exdate).ok_or_else(|| unsupported(at, name))?;
+                    event.exdates.push(exdate);
+                }
+            }
+            "RECURRENCE-ID" => {
+                let (ts, _) = datetime(value).ok_or_else(|| unsupported(at, name))?;
+                event.recurrence_id = Some(ts);
+            }
+            "RDATE" | "EXRULE" => return Err(unsupported(at, name)),
+            _ => {}
+        }
+    }
+    Ok(Some(event))
+}
+
+/// Splits a content line into the name of the property, its `TZID`
+/// parameter if any, and its value, leaving out the other parameters.
+fn property(line: &str) -> Option<(&str, Option<&str>, &str)> {
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(idx, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        (c == ':' && !quoted).then_some(idx)
+    })?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parameters = head.split(';');
+    let name = parameters.next()?;
+    let tzid = parameters
+        .find_map(|parameter| parameter.strip_prefix("TZID="))
+        .map(|tzid| tzid.trim_matches('"'));
+    Some((name, tzid, value))
+}
+
+/// Returns the name of the local time zone, e.g. `Europe/Madrid`, as set by
+/// `TZ` or else by the `/etc/localtime` link into the zone database.
+fn local_zone() -> Option<String> {
+    if let Ok(tz) = std::env::var("TZ") {
+        return Some(tz.strip_prefix(':').unwrap_or(&tz).to_string());
+    }
+    let target = std::fs::read_link("/etc/localtime").ok()?;
+    let target = target.to_str()?;
+    let (_, zone) = target.split_once("zoneinfo/")?;
+    Some(zone.to_string())
+}
+
+/// Parses a date, or a date with a time, in UTC or else in local time.
+///
+/// Returns the local time, and whether it is a date.
+fn datetime(value: &str) -> Option<(NaiveDateTime, bool)> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+        return Some((date.and_hms_opt(0, 0, 0)?, true));
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let ts = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some((
+            Utc.from_utc_datetime(&ts)
+                .with_timezone(&Local)
+                .naive_local(),
+            false,
+        ));
+    }
+    let ts = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Some((ts, false))
+}
+
+/// Parses a positive duration, e.g. `PT1H30M` or `P1W`.
+fn duration(value: &str) -> Option<TimeDelta> {
+    let value = value.strip_prefix('+').unwrap_or(value).strip_prefix('P')?;
+    let (date, time) = value.split_once('T').unwrap_or((value, ""));
+
+    let mut total = TimeDelta::zero();
+    for (part, units) in [(date, "WD"), (time, "HMS")] {
+        let mut amount = String::new();
+        for c in part.chars() {
+            if c.is_ascii_digit() {
+                amount.push(c);
+                continue;
+            }
+            if !units.contains(c) {
+                return None;
+            }
+            let n: i64 = amount.parse().ok()?;
+            amount.clear();
+            total += match c {
+                'W' => TimeDelta::weeks(n),
+                'D' => TimeDelta::days(n),
+                'H' => TimeDelta::hours(n),
+                'M' => TimeDelta::minutes(n),
+                _ => TimeDelta::seconds(n),
+            };
+        }
+        if !amount.is_empty() {
+            return None;
+        }
+    }
+    Some(total)
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+fn local(ts: NaiveDateTime) -> Option<DateTime<Local>> {
+    Local.from_local_datetime(&ts).earliest()
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test::d;
+
+    fn starts(
+        calendar: &Calendar,
+        start: DateTime<Local>,
+        end: DateTime<Local>,
+    ) -> Vec<DateTime<Local>> {
+        calendar.busy(start, end).iter().map(Busy::start).collect()
+    }
+
+    #[test]
+    fn test_busy() {
+        let sut = Calendar::parse(
+            "\
+BEGIN:VCALENDAR
+BEGIN:VTIMEZONE
+TZID:Europe/Madrid
+BEGIN:STANDARD
+DTSTART:19701025T030000
+END:STANDARD
+END:VTIMEZONE
+BEGIN:VEVENT
+UID:standup
+SUMMARY:Stand-up\\, daily
+DTSTART;TZID=Europe/Madrid:20251020T093000
+DURATION:PT15M
+RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;COUNT=5
+EXDATE;TZID=Europe/Madrid:20251022T093000
+END:VEVENT
+BEGIN:VEVENT
+UID:standup
+RECURRENCE-ID;TZID=Europe/Madrid:20251024T093000
+SUMMARY:Stand-up
+DTSTART;TZID=Europe/Madrid:20251024T110000
+DTEND;TZID=Europe/Madrid:20251024T111500
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Dentist
+DTSTART:20251023T160000
+DTEND:20251023T170000
+BEGIN:VALARM
+DURATION:PT1H
+END:VALARM
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Holiday
+DTSTART;VALUE=DATE:20251101
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Maybe
+DTSTART:20251023T100000
+DTEND:20251023T110000
+TRANSP:TRANSPARENT
+END:VEVENT
+END:VCALENDAR
+",
+            Some("Europe/Madrid"),
+        )
+        .unwrap();
+
+        let busy = sut.busy(d(2025, 10, 20, 0, 0, 0), d(2025, 11, 3, 0, 0, 0));
+        assert_eq!(
+            vec![
+                Busy::new(
+                    "Stand-up, daily".to_string(),
+                    d(2025, 10, 20, 9, 30, 0),
+                    d(2025, 10, 20, 9, 45, 0)
+                ),
+                Busy::new(
+                    "Dentist".to_string(),
+                    d(2025, 10, 23, 16, 0, 0),
+                    d(2025, 10, 23, 17, 0, 0)
+                ),
+                Busy::new(
+                    "Stand-up".to_string(),
+                    d(2025, 10, 24, 11, 0, 0),
+                    d(2025, 10, 24, 11, 15, 0)
+                ),
+                Busy::new(
+                    "Stand-up, daily".to_string(),
+                    d(2025, 10, 27, 9, 30, 0),
+                    d(2025, 10, 27, 9, 45, 0)
+                ),
+                Busy::new(
+                    "Stand-up, daily".to_string(),
+                    d(2025, 10, 29, 9, 30, 0),
+                    d(2025, 10, 29, 9, 45, 0)
+                ),
+                Busy::new(
+                    "Holiday".to_string(),
+                    d(2025, 11, 1, 0, 0, 0),
+                    d(2025, 11, 2, 0, 0, 0)
+                ),
+            ],
+            busy
+        );
+
+        // Only the occurrences that overlap the window.
+        assert_eq!(
+            vec![d(2025, 10, 23, 16, 0, 0), d(2025, 10, 24, 11, 0, 0)],
+            starts(&sut, d(2025, 10, 23, 16, 30, 0), d(2025, 10, 27, 0, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let calendar = |rule: &str| -> Calendar {
+            format!(
+                "BEGIN:VEVENT\nDTSTART:20250131T100000\nDURATION:PT1H\nRRULE:{rule}\nEND:VEVENT\n"
+            )
+            .parse()
+            .unwrap()
+        };
+        let (start, end) = (d(2025, 1, 1, 0, 0, 0), d(2026, 6, 1, 0, 0, 0));
+
+        let sut = calendar("FREQ=DAILY;INTERVAL=2;UNTIL=20250206T235959Z");
+        assert_eq!(4, starts(&sut, start, end).len());
+
+        // Months without a 31st are skipped.
+        let sut = calendar("FREQ=MONTHLY;COUNT=3");
+        assert_eq!(
+            vec![
+                d(2025, 1, 31, 10, 0, 0),
+                d(2025, 3, 31, 10, 0, 0),
+                d(2025, 5, 31, 10, 0, 0)
+            ],
+            starts(&sut, start, end)
+        );
+
+        let sut = calendar("FREQ=YEARLY");
+        assert_eq!(
+            vec![d(2025, 1, 31, 10, 0, 0), d(2026, 1, 31, 10, 0, 0)],
+            starts(&sut, start, end)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Err(IcsError::Missing {
+                line: 1,
+                name: "DTSTART"
+            }),
+            "BEGIN:VEVENT\nSUMMARY:Lunch\nEND:VEVENT".parse::<Calendar>()
+        );
+        assert_eq!(
+            Err(IcsError::Property {
+                line: 3,
+                name: "RRULE".to_string()
+            }),
+            "BEGIN:VEVENT\nDTSTART:20251023T100000\nRRULE:FREQ=MONTHLY;BYDAY=2MO\nEND:VEVENT"
+                .parse::<Calendar>()
+        );
+        assert_eq!(
+            Err(IcsError::Syntax(2)),
+            "BEGIN:VEVENT\nEND:VCALENDAR".parse::<Calendar>()
+        );
+
+        let event = "BEGIN:VEVENT\nDTSTART;TZID=\"America/New_York\":20251023T100000\nEND:VEVENT";
+        let error = IcsError::TimeZone {
+            line: 2,
+            tzid: "America/New_York".to_string(),
+        };
+        assert_eq!(
+            Err(error.clone()),
+            Calendar::parse(event, Some("Europe/Madrid"))
+        );
+        assert_eq!(Err(error.clone()), Calendar::parse(event, None));
+        assert_eq!(
+            "line 2: time zone America/New_York is not known to be the local one",
+            error.to_string()
+        );
+        assert!(Calendar::parse(event, Some("America/New_York")).is_ok());
+    }
+}
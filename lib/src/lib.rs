@@ -1,5 +1,9 @@
-mod sequencer;
-mod types;
+pub mod data;
+pub mod ics;
+pub mod planner;
+pub mod sequencer;
+pub mod simulator;
+pub mod types;
 
 #[cfg(test)]
 pub mod test {
@@ -7,10 +11,57 @@ pub mod test {
     use chrono::Local;
     use chrono::TimeZone;
 
+    use crate::types::Blueprint;
+    use crate::types::Duration;
+    use crate::types::HourSlot;
+    use crate::types::Priority;
+    use crate::types::Recurrence;
+    use crate::types::Slot;
+
     // Generate datetime on tests, with less verbosity.
     pub fn d(year: i32, month: u32, day: u32, hour: u32, minute: u32, sec: u32) -> DateTime<Local> {
         Local
             .with_ymd_and_hms(year, month, day, hour, minute, sec)
             .unwrap()
     }
+
+    /// Creates a blueprint `id` that takes an hour.
+    pub fn blueprint(
+        id: &str,
+        priority: Priority,
+        recurrence: Recurrence,
+        slot: Slot,
+    ) -> Blueprint {
+        blueprint_for(id, Duration::hours(1), priority, recurrence, slot)
+    }
+
+    /// Creates a blueprint `id` that takes `duration`.
+    pub fn blueprint_for(
+        id: &str,
+        duration: Duration,
+        priority: Priority,
+        recurrence: Recurrence,
+        slot: Slot,
+    ) -> Blueprint {
+        Blueprint::new(
+            id.to_string(),
+            format!("Task {id}"),
+            duration,
+            priority,
+            recurrence,
+            slot,
+        )
+    }
+
+    /// Creates a blueprint `id` that takes an hour every day at `hour`.
+    pub fn daily(id: &str, priority: Priority, hour: u32) -> Blueprint {
+        blueprint(
+            id,
+            priority,
+            Recurrence::Period {
+                spacing: Duration::days(1),
+            },
+            Slot::Hour(HourSlot::Fixed { hour }),
+        )
+    }
 }
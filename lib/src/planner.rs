@@ -0,0 +1,1714 @@
+mod load;
+mod schedule;
+mod timeline;
+mod track;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::TimeDelta;
+use chrono::Timelike;
+
+use crate::planner::schedule::Schedule;
+use crate::planner::timeline::Span;
+use crate::planner::timeline::Timeline;
+use crate::planner::track::Track;
+use crate::planner::track::buffers;
+use crate::planner::track::span_of;
+use crate::types::Duration;
+use crate::types::experimental::book::Book;
+use crate::types::experimental::busy::Busy;
+use crate::types::experimental::journal::Commit;
+use crate::types::experimental::journal::Journal;
+use crate::types::experimental::pins::Pin;
+use crate::types::experimental::pins::Pins;
+use crate::types::experimental::plan::Displacement;
+use crate::types::experimental::plan::Plan;
+use crate::types::experimental::plan::Reason;
+use crate::types::experimental::plan::StalePin;
+use crate::types::experimental::plan::Staleness;
+use crate::types::experimental::plan_entry::PlanEntry;
+
+/// Materializes the blueprints of a [Book] into a [Plan].
+///
+/// The planner sweeps time forward on full hours, from the first one after
+/// `start`, jumping straight to the next timestamp that any blueprint may
+/// accept. At each stop, blueprints place their next occurrence in priority
+/// order, then earliest deadline first, wherever all the constraints of the
+/// book hold, preempting entries of lower priority that are in the way.
+pub struct Planner<'a> {
+    book: &'a Book,
+    journal: &'a Journal,
+    previous: Option<&'a Plan>,
+    pins: Option<&'a Pins>,
+    busy: &'a [Busy],
+    explain: bool,
+}
+
+impl<'a> Planner<'a> {
+    pub const fn new(book: &'a Book, journal: &'a Journal) -> Self {
+        Self {
+            book,
+            journal,
+            previous: None,
+            pins: None,
+            busy: &[],
+            explain: false,
+        }
+    }
+
+    /// Keeps the entries of the `previous` plan wherever they are still
+    /// valid, rather than planning them from scratch.
+    pub const fn with_previous(mut self, previous: &'a Plan) -> Self {
+        self.previous = Some(previous);
+        self
+    }
+
+    /// Fixes the occurrences of the blueprints at the times set by `pins`,
+    /// even outside the slot of their blueprint. Each pin stands for the
+    /// occurrence pending by its time, and is never preempted.
+    ///
+    /// Pins of deleted blueprints, in the past, or that overlap other pins,
+    /// busy time or blackouts are reported as stale in the [Plan]. Pins past
+    /// the horizon are left out.
+    pub const fn with_pins(mut self, pins: &'a Pins) -> Self {
+        self.pins = Some(pins);
+        self
+    }
+
+    /// Keeps the entries off the `busy` time, e.g. as imported from other
+    /// calendars.
+    pub const fn with_busy(mut self, busy: &'a [Busy]) -> Self {
+        self.busy = busy;
+        self
+    }
+
+    /// Explains, for each blueprint, the candidates that were considered,
+    /// what rejected them, and the final decision. See
+    /// [explanations](Plan::explanations).
+    pub const fn with_explanations(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    /// Plans the blueprints over `[start, start + horizon)`.
+    ///
+    /// Entries in the resulting [Plan] are ordered by time. Occurrences that
+    /// are overdue at `start` are caught up with, following the
+    /// [CatchUp](crate::types::CatchUp) policy of their blueprint, and the
+    /// plan reports them, along with those at risk of missing their deadline
+    /// and the entries that were displaced.
+    pub fn plan(&self, start: DateTime<Local>, horizon: Duration) -> Plan {
+        self.run(start, horizon, self.previous, None)
+    }
+
+    /// Refreshes the `previous` plan over `[start, start + horizon)` after
+    /// `commit` was recorded in the journal.
+    ///
+    /// Only the blueprint of the commit is planned again from scratch. The
+    /// entries of any other blueprint are kept as they are, unless they get
+    /// displaced by it, or their blueprint could take the time it no longer
+    /// needs. In both cases, that blueprint is planned again from there on.
+    ///
+    /// The journal of the planner must already hold `commit`.
+    pub fn replan(
+        &self,
+        previous: &Plan,
+        commit: &Commit,
+        start: DateTime<Local>,
+        horizon: Duration,
+    ) -> Plan {
+        self.run(start, horizon, Some(previous), Some(commit))
+    }
+
+    /// Plans over `[start, start + horizon)`, counting the entries of the
+    /// `previous` plan that moved. After a `commit`, the entries of the
+    /// `previous` plan that it does not affect are restored as they were.
+    fn run(
+        &self,
+        start: DateTime<Local>,
+        horizon: Duration,
+        previous: Option<&Plan>,
+        commit: Option<&Commit>,
+    ) -> Plan {
+        let end = start + horizon.timedelta();
+        let (pins, stale_pins) = self.pins(start, end);
+        let mut tracks = self.tracks(start, &pins);
+        let displaced = match (previous, commit) {
+            (Some(previous), Some(commit)) => restore(&mut tracks, previous, commit),
+            _ => Vec::new(),
+        };
+
+        let mut schedule = Schedule::new(tracks, self.book.capacity())
+            .with_displaced(displaced)
+            .with_pull_forward(self.pull_forward())
+            .with_leveling(self.leveling())
+            .with_anti_affinities(self.book.anti_affinities())
+            .with_blackouts(self.book.blackouts())
+            .with_busy(self.busy);
+        if self.explain {
+            schedule = schedule.with_explanations();
+        }
+        sweep(&mut schedule, start, end);
+        let explanations = schedule.explanations(start, end);
+        let plan = schedule
+            .into_plan(end)
+            .with_stale_pins(stale_pins)
+            .with_explanations(explanations);
+        match previous {
+            Some(previous) => {
+                let moved = moved(previous, &plan, start, end);
+                plan.with_moved(moved)
+            }
+            None => plan,
+        }
+    }
+
+    /// Splits the pins within `[start, end)` into those that can be planned,
+    /// and those that are stale. Pins from `end` on are left for later plans.
+    fn pins(&self, start: DateTime<Local>, end: DateTime<Local>) -> (Vec<&'a Pin>, Vec<StalePin>) {
+        let mut pins = Vec::new();
+        let mut stale_pins = Vec::new();
+        let mut taken = Timeline::default();
+        for pin in self.pins.map_or(&[][..], Pins::pins) {
+            let Some(blueprint) = self.book.get(pin.blueprint_id()) else {
+                stale_pins.push(StalePin::new(pin.clone(), Staleness::Deleted));
+                continue;
+            };
+            if pin.pinned_for() < start {
+                stale_pins.push(StalePin::new(pin.clone(), Staleness::Past));
+                continue;
+            }
+            if pin.pinned_for() >= end {
+                continue;
+            }
+            let (before, after) = buffers(blueprint, self.book.min_gap());
+            let pinned = Span::new(
+                pin.pinned_for(),
+                pin.pinned_for() + blueprint.estimated_duration().timedelta(),
+            );
+            let span = Span::new(pinned.start - before, pinned.end + after);
+            if self.book.blackouts().iter().any(|blackout| {
+                blackout.blocks(blueprint)
+                    && pinned.overlaps(&Span::new(blackout.start(), blackout.end()))
+            }) {
+                stale_pins.push(StalePin::new(pin.clone(), Staleness::Blackout));
+                continue;
+            }
+            if self
+                .busy
+                .iter()
+                .any(|busy| span.overlaps(&Span::new(busy.start(), busy.end())))
+            {
+                stale_pins.push(StalePin::new(pin.clone(), Staleness::Busy));
+                continue;
+            }
+            if !taken.is_free(&span) {
+                stale_pins.push(StalePin::new(pin.clone(), Staleness::Overlapped));
+                continue;
+            }
+            taken.insert(span, ());
+            pins.push(pin);
+        }
+        (pins, stale_pins)
+    }
+
+    fn leveling(&self) -> Option<TimeDelta> {
+        self.book.leveling().map(|tolerance| tolerance.timedelta())
+    }
+
+    fn pull_forward(&self) -> TimeDelta {
+        self.book
+            .pull_forward()
+            .map_or_else(TimeDelta::zero, |pull_forward| pull_forward.timedelta())
+    }
+
+    fn tracks(&self, start: DateTime<Local>, pins: &[&Pin]) -> Vec<Track> {
+        self.book
+            .spawn_sequencers(self.journal)
+            .into_iter()
+            .map(|(blueprint, sequencer)| {
+                let previous = self.previous.map_or_else(Vec::new, |previous| {
+                    previous
+                        .entries()
+                        .iter()
+                        .filter(|entry| entry.blueprint_id() == blueprint.id())
+                        .map(PlanEntry::planned_for)
+                        .filter(|planned_for| *planned_for >= start)
+                        .collect()
+                });
+                let pins = pins
+                    .iter()
+                    .filter(|pin| pin.blueprint_id() == blueprint.id())
+                    .map(|pin| pin.pinned_for())
+                    .collect();
+                let progress = self
+                    .journal
+                    .get_progress_for(blueprint.id(), blueprint.estimated_duration());
+                let completed_at = self
+                    .journal
+                    .get_completions_for(blueprint.id(), blueprint.estimated_duration())
+                    .last()
+                    .map(|commit| commit.committed_at());
+                let mut track = Track::new(blueprint, sequencer)
+                    .with_previous(previous)
+                    .with_pins(pins)
+                    .with_progress(progress)
+                    .with_min_gap(self.book.min_gap())
+                    .with_completed_at(completed_at);
+                track.catch_up(start);
+                track
+            })
+            .collect()
+    }
+}
+
+/// Restores the entries of the `previous` plan to the `tracks` that `commit`
+/// does not affect, and rolls back those that could take the time its
+/// blueprint no longer needs.
+///
+/// Returns the displacements of the `previous` plan that still hold.
+fn restore(tracks: &mut [Track], previous: &Plan, commit: &Commit) -> Vec<Displacement> {
+    let id = commit.blueprint_id();
+    for track in tracks.iter_mut() {
+        let track_id = track.blueprint().id().to_string();
+        let entries = || {
+            previous
+                .entries()
+                .iter()
+                .filter(|entry| entry.blueprint_id() == track_id)
+        };
+        if track_id != id && track.restores_pins(entries()) {
+            track.restore(entries());
+        }
+    }
+
+    for span in previous
+        .entries()
+        .iter()
+        .filter(|entry| entry.blueprint_id() == id)
+        .map(span_of)
+    {
+        for track in tracks.iter_mut() {
+            if track.is_frozen() && track.could_take(&span) {
+                track.rollback(span.start);
+            }
+        }
+    }
+
+    previous
+        .displaced()
+        .iter()
+        .filter(|displacement| {
+            let Reason::Preempted { by, .. } = displacement.reason();
+            by != id && displacement.entry().blueprint_id() != id
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns how many entries of the `previous` plan within `[start, end)`
+/// are no longer planned for the same time in `plan`.
+fn moved(previous: &Plan, plan: &Plan, start: DateTime<Local>, end: DateTime<Local>) -> usize {
+    previous
+        .entries()
+        .iter()
+        .filter(|entry| start <= entry.planned_for() && entry.planned_for() < end)
+        .filter(|entry| {
+            !plan.entries().iter().any(|kept| {
+                kept.blueprint_id() == entry.blueprint_id()
+                    && kept.planned_for() == entry.planned_for()
+            })
+        })
+        .count()
+}
+
+/// Walks time forward from `start` until `end`, letting the tracks of the
+/// schedule place their occurrences.
+fn sweep(schedule: &mut Schedule, start: DateTime<Local>, end: DateTime<Local>) {
+    let mut ts = ceil_hour(start);
+    while ts < end {
+        for idx in schedule.order() {
+            schedule.try_place(idx, ts);
+        }
+        match schedule.next_candidate(ts + TimeDelta::hours(1)) {
+            Some(next) => ts = ceil_hour(next),
+            None => break,
+        }
+    }
+    schedule.commit_pins(end);
+}
+
+/// Rounds `ts` up to the next full hour, unless it already is one.
+fn ceil_hour(ts: DateTime<Local>) -> DateTime<Local> {
+    let floor = ts
+        .with_nanosecond(0)
+        .and_then(|ts| ts.with_second(0))
+        .and_then(|ts| ts.with_minute(0))
+        .unwrap_or(ts);
+    if floor < ts {
+        floor + TimeDelta::hours(1)
+    } else {
+        floor
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test::blueprint;
+    use crate::test::blueprint_for;
+    use crate::test::d;
+    use crate::test::daily;
+    use crate::types::Blueprint;
+    use crate::types::CatchUp;
+    use crate::types::DayOfWeek;
+    use crate::types::Deadline;
+    use crate::types::Dependency;
+    use crate::types::HourSlot;
+    use crate::types::Priority;
+    use crate::types::Recurrence;
+    use crate::types::Slot;
+    use crate::types::TimeUnit;
+    use crate::types::WeekSlot;
+    use crate::types::experimental::anti_affinity::AntiAffinity;
+    use crate::types::experimental::anti_affinity::Separation;
+    use crate::types::experimental::anti_affinity::Target;
+    use crate::types::experimental::blackout::Blackout;
+    use crate::types::experimental::capacity::Capacity;
+    use crate::types::experimental::explanation::Attempt;
+    use crate::types::experimental::explanation::Decision;
+    use crate::types::experimental::explanation::Explanation;
+    use crate::types::experimental::explanation::Rejection;
+    use crate::types::experimental::journal::Postpone;
+    use crate::types::experimental::plan::AtRisk;
+    use crate::types::experimental::plan::Overdue;
+
+    fn planned_for(plan: &Plan, id: &str) -> Vec<DateTime<Local>> {
+        plan.entries()
+            .iter()
+            .filter(|entry| entry.blueprint_id() == id)
+            .map(PlanEntry::planned_for)
+            .collect()
+    }
+
+    #[test]
+    fn test_ceil_hour() {
+        assert_eq!(
+            d(2025, 10, 23, 14, 0, 0),
+            ceil_hour(d(2025, 10, 23, 14, 0, 0))
+        );
+        assert_eq!(
+            d(2025, 10, 23, 15, 0, 0),
+            ceil_hour(d(2025, 10, 23, 14, 0, 1))
+        );
+        assert_eq!(
+            d(2025, 10, 24, 0, 0, 0),
+            ceil_hour(d(2025, 10, 23, 23, 30, 0))
+        );
+    }
+
+    #[test]
+    fn test_plan_yearly_on_weekends() {
+        let book = Book::new(vec![blueprint(
+            "vac",
+            Priority::Idle,
+            Recurrence::Period {
+                spacing: Duration::of(1, TimeUnit::Year),
+            },
+            Slot::Week(WeekSlot::weekend()),
+        )])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        // Thursday.
+        let plan = sut.plan(d(2025, 10, 23, 14, 0, 0), Duration::of(3, TimeUnit::Year));
+
+        assert_eq!(
+            vec![
+                d(2025, 10, 25, 0, 0, 0),
+                d(2026, 10, 25, 0, 0, 0),
+                d(2027, 10, 30, 0, 0, 0),
+            ],
+            planned_for(&plan, "vac")
+        );
+    }
+
+    #[test]
+    fn test_plan_resumes_from_journal() {
+        let book = Book::new(vec![blueprint(
+            "dentist",
+            Priority::Norm,
+            Recurrence::Period {
+                spacing: Duration::of(1, TimeUnit::Year),
+            },
+            Slot::Hour(HourSlot::Fixed { hour: 10 }),
+        )])
+        .unwrap();
+        let journal = Journal::new(vec![Commit::completed(
+            "dentist".to_string(),
+            d(2025, 3, 1, 10, 0, 0),
+        )]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 14, 0, 0), Duration::of(1, TimeUnit::Year));
+
+        assert_eq!(vec![d(2026, 3, 1, 10, 0, 0)], planned_for(&plan, "dentist"));
+    }
+
+    #[test]
+    fn test_plan_skips_completed_occurrences() {
+        let book = Book::new(vec![
+            blueprint(
+                "once",
+                Priority::Norm,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            ),
+            blueprint(
+                "twice",
+                Priority::Norm,
+                Recurrence::Times {
+                    count: 3,
+                    spacing: Duration::days(1),
+                },
+                Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            ),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![
+            Commit::completed("once".to_string(), d(2025, 10, 20, 10, 0, 0)),
+            Commit::completed("twice".to_string(), d(2025, 10, 20, 10, 0, 0)),
+        ]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 14, 0, 0), Duration::days(7));
+
+        assert_eq!(Vec::<DateTime<Local>>::new(), planned_for(&plan, "once"));
+        assert_eq!(
+            vec![d(2025, 10, 24, 10, 0, 0), d(2025, 10, 25, 10, 0, 0)],
+            planned_for(&plan, "twice")
+        );
+    }
+
+    #[test]
+    fn test_plan_orders_by_time_then_priority() {
+        let daily = Recurrence::Period {
+            spacing: Duration::days(1),
+        };
+        let book = Book::new(vec![
+            blueprint(
+                "low",
+                Priority::Idle,
+                daily,
+                Slot::Hour(HourSlot::Range { start: 9, stop: 10 }),
+            ),
+            blueprint(
+                "high",
+                Priority::Crit,
+                daily,
+                Slot::Hour(HourSlot::Fixed { hour: 9 }),
+            ),
+            blueprint(
+                "early",
+                Priority::Idle,
+                daily,
+                Slot::Hour(HourSlot::Fixed { hour: 8 }),
+            ),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 0, 0, 0), Duration::days(1));
+
+        let order: Vec<_> = plan
+            .entries()
+            .iter()
+            .map(|entry| (entry.blueprint_id(), entry.planned_for()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("early", d(2025, 10, 23, 8, 0, 0)),
+                ("high", d(2025, 10, 23, 9, 0, 0)),
+                ("low", d(2025, 10, 23, 10, 0, 0)),
+            ],
+            order
+        );
+    }
+
+    #[test]
+    fn test_plan_never_overlaps_entries() {
+        let weekly = Recurrence::Period {
+            spacing: Duration::days(7),
+        };
+        let morning = Slot::Hour(HourSlot::Range { start: 9, stop: 12 });
+        let book = Book::new(vec![
+            blueprint_for("a", Duration::hours(2), Priority::High, weekly, morning),
+            blueprint_for("b", Duration::minutes(90), Priority::Norm, weekly, morning),
+            blueprint_for("c", Duration::hours(1), Priority::Idle, weekly, morning),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 0, 0, 0), Duration::days(1));
+
+        assert_eq!(vec![d(2025, 10, 23, 9, 0, 0)], planned_for(&plan, "a"));
+        assert_eq!(vec![d(2025, 10, 23, 11, 0, 0)], planned_for(&plan, "b"));
+        assert_eq!(Vec::<DateTime<Local>>::new(), planned_for(&plan, "c"));
+    }
+
+    #[test]
+    fn test_plan_fits_duration_within_slot() {
+        let book = Book::new(vec![blueprint_for(
+            "long",
+            Duration::hours(3),
+            Priority::Norm,
+            Recurrence::Once,
+            Slot::Hour(HourSlot::Range {
+                start: 10,
+                stop: 13,
+            }),
+        )])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 11, 30, 0), Duration::days(2));
+
+        assert_eq!(vec![d(2025, 10, 24, 10, 0, 0)], planned_for(&plan, "long"));
+    }
+
+    #[test]
+    fn test_plan_preempts_lower_priority() {
+        let saturday_morning = Slot::Hour(HourSlot::Range { start: 9, stop: 12 });
+        let book = Book::new(vec![
+            blueprint_for(
+                "vac",
+                Duration::hours(3),
+                Priority::Idle,
+                Recurrence::Once,
+                saturday_morning,
+            ),
+            blueprint_for(
+                "dentist",
+                Duration::hours(1),
+                Priority::Crit,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            ),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 25, 9, 0, 0), Duration::days(2));
+
+        assert_eq!(
+            vec![d(2025, 10, 25, 10, 0, 0)],
+            planned_for(&plan, "dentist")
+        );
+        assert_eq!(vec![d(2025, 10, 26, 9, 0, 0)], planned_for(&plan, "vac"));
+
+        let displaced: Vec<_> = plan
+            .displaced()
+            .iter()
+            .map(|displacement| {
+                (
+                    displacement.entry().blueprint_id(),
+                    displacement.entry().planned_for(),
+                    displacement.reason().clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![(
+                "vac",
+                d(2025, 10, 25, 9, 0, 0),
+                Reason::Preempted {
+                    by: "dentist".to_string(),
+                    priority: Priority::Crit,
+                }
+            )],
+            displaced
+        );
+    }
+
+    #[test]
+    fn test_plan_does_not_preempt_same_priority() {
+        let book = Book::new(vec![
+            blueprint_for(
+                "first",
+                Duration::hours(3),
+                Priority::Norm,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Range { start: 9, stop: 12 }),
+            ),
+            blueprint_for(
+                "second",
+                Duration::hours(1),
+                Priority::Norm,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            ),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 25, 9, 0, 0), Duration::days(2));
+
+        assert_eq!(vec![d(2025, 10, 25, 9, 0, 0)], planned_for(&plan, "first"));
+        assert_eq!(
+            vec![d(2025, 10, 26, 10, 0, 0)],
+            planned_for(&plan, "second")
+        );
+        assert!(plan.displaced().is_empty());
+    }
+
+    #[test]
+    fn test_plan_jumps_over_dead_time() {
+        let book = Book::new(vec![
+            blueprint(
+                "certificate",
+                Priority::Crit,
+                Recurrence::Period {
+                    spacing: Duration::of(8, TimeUnit::Year),
+                },
+                Slot::Week(WeekSlot::workdays()),
+            ),
+            blueprint(
+                "vac",
+                Priority::Idle,
+                Recurrence::Period {
+                    spacing: Duration::of(1, TimeUnit::Year),
+                },
+                Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            ),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![Commit::completed(
+            "certificate".to_string(),
+            d(2020, 1, 1, 9, 30, 0),
+        )]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 14, 0, 0), Duration::of(10, TimeUnit::Year));
+
+        // Due at 09:30, planned on the next full hour.
+        assert_eq!(
+            vec![d(2027, 12, 30, 10, 0, 0)],
+            planned_for(&plan, "certificate")
+        );
+        assert_eq!(10, planned_for(&plan, "vac").len());
+    }
+
+    #[test]
+    fn test_plan_spills_over_capacity() {
+        let weekly = Recurrence::Period {
+            spacing: Duration::days(7),
+        };
+        let weekend = Slot::Week(WeekSlot::weekend());
+        let book = Book::new(vec![
+            blueprint_for(
+                "gutters",
+                Duration::hours(3),
+                Priority::High,
+                weekly,
+                weekend,
+            ),
+            blueprint_for(
+                "garage",
+                Duration::hours(2),
+                Priority::Norm,
+                weekly,
+                weekend,
+            ),
+            blueprint_for(
+                "windows",
+                Duration::hours(2),
+                Priority::Idle,
+                weekly,
+                weekend,
+            ),
+        ])
+        .unwrap()
+        .with_capacity(
+            Capacity::unlimited()
+                .with_weekday(DayOfWeek::Sat, Duration::hours(4))
+                .with_weekly(Duration::hours(6)),
+        );
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        // Saturday.
+        let plan = sut.plan(d(2025, 10, 25, 0, 0, 0), Duration::days(2));
+
+        assert_eq!(
+            vec![d(2025, 10, 25, 0, 0, 0)],
+            planned_for(&plan, "gutters")
+        );
+        assert_eq!(vec![d(2025, 10, 26, 0, 0, 0)], planned_for(&plan, "garage"));
+        assert_eq!(Vec::<DateTime<Local>>::new(), planned_for(&plan, "windows"));
+    }
+
+    #[test]
+    fn test_plan_earliest_deadline_first() {
+        let weekly = Recurrence::Period {
+            spacing: Duration::days(7),
+        };
+        let morning = Slot::Hour(HourSlot::Fixed { hour: 9 });
+        let book = Book::new(vec![
+            blueprint("late", Priority::Norm, weekly, morning)
+                .with_deadline(Deadline::At(d(2025, 10, 30, 0, 0, 0))),
+            blueprint("none", Priority::Norm, weekly, morning),
+            blueprint("soon", Priority::Norm, weekly, morning)
+                .with_deadline(Deadline::At(d(2025, 10, 24, 0, 0, 0))),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 0, 0, 0), Duration::days(3));
+
+        assert_eq!(vec![d(2025, 10, 23, 9, 0, 0)], planned_for(&plan, "soon"));
+        assert_eq!(vec![d(2025, 10, 24, 9, 0, 0)], planned_for(&plan, "late"));
+        assert_eq!(vec![d(2025, 10, 25, 9, 0, 0)], planned_for(&plan, "none"));
+        assert!(plan.at_risk().is_empty());
+    }
+
+    #[test]
+    fn test_plan_reports_at_risk() {
+        let book = Book::new(vec![
+            blueprint(
+                "certificate",
+                Priority::Crit,
+                Recurrence::Once,
+                Slot::Week(WeekSlot::workdays()),
+            )
+            .with_deadline(Deadline::At(d(2025, 10, 25, 12, 0, 0))),
+            blueprint(
+                "dentist",
+                Priority::Norm,
+                Recurrence::Period {
+                    spacing: Duration::of(1, TimeUnit::Year),
+                },
+                Slot::Week(WeekSlot::weekend()),
+            )
+            .with_deadline(Deadline::Within(Duration::of(1, TimeUnit::Year))),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![Commit::completed(
+            "dentist".to_string(),
+            d(2024, 10, 21, 10, 0, 0),
+        )]);
+        let sut = Planner::new(&book, &journal);
+
+        // Saturday.
+        let plan = sut.plan(d(2025, 10, 25, 0, 0, 0), Duration::days(5));
+
+        assert_eq!(
+            vec![
+                AtRisk::new(
+                    "certificate".to_string(),
+                    d(2025, 10, 25, 12, 0, 0),
+                    Some(d(2025, 10, 27, 0, 0, 0))
+                ),
+                AtRisk::new(
+                    "dentist".to_string(),
+                    d(2025, 10, 21, 10, 0, 0),
+                    Some(d(2025, 10, 25, 0, 0, 0))
+                ),
+            ],
+            plan.at_risk()
+        );
+    }
+
+    #[test]
+    fn test_plan_within_lead_time() {
+        let book = Book::new(vec![
+            blueprint(
+                "certificate",
+                Priority::Crit,
+                Recurrence::Lead {
+                    period: Duration::of(8, TimeUnit::Year),
+                    earliest: Duration::of(6, TimeUnit::Month),
+                    latest: Duration::of(3, TimeUnit::Month),
+                },
+                Slot::Week(WeekSlot::workdays()),
+            )
+            .with_expiry(d(2026, 3, 1, 0, 0, 0)),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 6, 1, 0, 0, 0), Duration::of(2, TimeUnit::Year));
+
+        // Six months before the expiry, on a Tuesday.
+        assert_eq!(
+            vec![d(2025, 9, 2, 0, 0, 0)],
+            planned_for(&plan, "certificate")
+        );
+        assert!(plan.at_risk().is_empty());
+    }
+
+    #[test]
+    fn test_plan_prefers_nominal() {
+        let about_yearly = Recurrence::About {
+            spacing: Duration::of(1, TimeUnit::Year),
+            early: Duration::days(30),
+            late: Duration::days(60),
+        };
+        let ten_am = Slot::Hour(HourSlot::Fixed { hour: 10 });
+        let book = Book::new(vec![
+            blueprint("dentist", Priority::Norm, about_yearly, ten_am),
+            blueprint("optician", Priority::High, about_yearly, ten_am),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![
+            Commit::completed("dentist".to_string(), d(2024, 11, 3, 10, 0, 0)),
+            Commit::completed("optician".to_string(), d(2024, 11, 3, 10, 0, 0)),
+        ]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 9, 1, 0, 0, 0), Duration::days(120));
+
+        assert_eq!(
+            vec![d(2025, 11, 3, 10, 0, 0)],
+            planned_for(&plan, "optician")
+        );
+        assert_eq!(
+            vec![d(2025, 11, 2, 10, 0, 0)],
+            planned_for(&plan, "dentist")
+        );
+    }
+
+    #[test]
+    fn test_plan_catches_up_with_overdue() {
+        let daily = Recurrence::Period {
+            spacing: Duration::days(1),
+        };
+        let evening = Slot::Hour(HourSlot::Range {
+            start: 18,
+            stop: 21,
+        });
+        let book = Book::new(vec![
+            blueprint("skip", Priority::Norm, daily, evening).with_catch_up(CatchUp::Skip),
+            blueprint("once", Priority::Norm, daily, evening).with_catch_up(CatchUp::Once),
+            blueprint("all", Priority::Norm, daily, evening).with_catch_up(CatchUp::All),
+        ])
+        .unwrap();
+        let last = d(2025, 10, 1, 19, 0, 0);
+        let journal = Journal::new(vec![
+            Commit::completed("skip".to_string(), last),
+            Commit::completed("once".to_string(), last),
+            Commit::completed("all".to_string(), last),
+        ]);
+        let sut = Planner::new(&book, &journal);
+
+        // Back from a trip, three days later.
+        let plan = sut.plan(d(2025, 10, 4, 18, 0, 0), Duration::hours(12));
+
+        assert_eq!(vec![d(2025, 10, 4, 19, 0, 0)], planned_for(&plan, "skip"));
+        assert_eq!(vec![d(2025, 10, 4, 18, 0, 0)], planned_for(&plan, "once"));
+        assert_eq!(
+            vec![d(2025, 10, 4, 20, 0, 0), d(2025, 10, 4, 21, 0, 0)],
+            planned_for(&plan, "all")
+        );
+        assert_eq!(
+            vec![
+                Overdue::new("skip".to_string(), 2, CatchUp::Skip, 0),
+                Overdue::new("once".to_string(), 2, CatchUp::Once, 1),
+                Overdue::new("all".to_string(), 2, CatchUp::All, 2),
+            ],
+            plan.overdue()
+        );
+    }
+
+    #[test]
+    fn test_plan_keeps_open_slot_due() {
+        let daily = Recurrence::Period {
+            spacing: Duration::days(1),
+        };
+        let evening = Slot::Hour(HourSlot::Range {
+            start: 18,
+            stop: 21,
+        });
+        let book = Book::new(vec![
+            blueprint("skip", Priority::Norm, daily, evening).with_catch_up(CatchUp::Skip),
+            blueprint("once", Priority::Norm, daily, evening).with_catch_up(CatchUp::Once),
+        ])
+        .unwrap();
+        let last = d(2025, 10, 3, 19, 0, 0);
+        let journal = Journal::new(vec![
+            Commit::completed("skip".to_string(), last),
+            Commit::completed("once".to_string(), last),
+        ]);
+        let sut = Planner::new(&book, &journal);
+
+        // Today's slot is still open.
+        let plan = sut.plan(d(2025, 10, 4, 19, 30, 0), Duration::hours(6));
+
+        assert_eq!(vec![d(2025, 10, 4, 20, 0, 0)], planned_for(&plan, "skip"));
+        assert_eq!(vec![d(2025, 10, 4, 21, 0, 0)], planned_for(&plan, "once"));
+        assert!(plan.overdue().is_empty());
+    }
+
+    #[test]
+    fn test_plan_escalates_postponed() {
+        let weekly = Recurrence::Period {
+            spacing: Duration::days(7),
+        };
+        let morning = Slot::Hour(HourSlot::Fixed { hour: 9 });
+        let book = Book::new(vec![
+            blueprint("review", Priority::Norm, weekly, morning),
+            blueprint("gym", Priority::Idle, weekly, morning),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![
+            Commit::completed("gym".to_string(), d(2025, 10, 13, 9, 0, 0)),
+            Commit::postponed(
+                "gym".to_string(),
+                d(2025, 10, 17, 9, 0, 0),
+                Postpone::Backoff,
+            ),
+            Commit::postponed(
+                "gym".to_string(),
+                d(2025, 10, 18, 9, 0, 0),
+                Postpone::Backoff,
+            ),
+            Commit::postponed(
+                "gym".to_string(),
+                d(2025, 10, 19, 9, 0, 0),
+                Postpone::Backoff,
+            ),
+        ]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 0, 0, 0), Duration::days(1));
+
+        // Postponed three times, it is now more important than the review.
+        assert_eq!(vec![d(2025, 10, 23, 9, 0, 0)], planned_for(&plan, "gym"));
+        assert_eq!(Vec::<DateTime<Local>>::new(), planned_for(&plan, "review"));
+    }
+
+    #[test]
+    fn test_replan_frees_time() {
+        let book = Book::new(vec![
+            blueprint(
+                "gym",
+                Priority::High,
+                Recurrence::Period {
+                    spacing: Duration::days(7),
+                },
+                Slot::Hour(HourSlot::Fixed { hour: 9 }),
+            ),
+            daily("review", Priority::Idle, 9),
+            daily("plants", Priority::Norm, 18),
+        ])
+        .unwrap();
+        let mut commits = vec![Commit::completed(
+            "gym".to_string(),
+            d(2025, 10, 13, 9, 0, 0),
+        )];
+        let start = d(2025, 10, 20, 0, 0, 0);
+        let previous =
+            Planner::new(&book, &Journal::new(commits.clone())).plan(start, Duration::days(2));
+        assert_eq!(
+            vec![d(2025, 10, 21, 9, 0, 0)],
+            planned_for(&previous, "review")
+        );
+
+        let commit = Commit::completed("gym".to_string(), d(2025, 10, 19, 18, 0, 0));
+        commits.push(commit.clone());
+        let journal = Journal::new(commits);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.replan(&previous, &commit, start, Duration::days(2));
+
+        assert_eq!(Vec::<DateTime<Local>>::new(), planned_for(&plan, "gym"));
+        assert_eq!(
+            vec![d(2025, 10, 20, 9, 0, 0), d(2025, 10, 21, 9, 0, 0)],
+            planned_for(&plan, "review")
+        );
+        assert_eq!(
+            planned_for(&previous, "plants"),
+            planned_for(&plan, "plants")
+        );
+        assert_eq!(sut.plan(start, Duration::days(2)).entries(), plan.entries());
+        assert_eq!(1, plan.moved());
+    }
+
+    #[test]
+    fn test_replan_displaces_lower_priority() {
+        let book = Book::new(vec![
+            blueprint(
+                "gym",
+                Priority::High,
+                Recurrence::Period {
+                    spacing: Duration::days(7),
+                },
+                Slot::Hour(HourSlot::Fixed { hour: 9 }),
+            ),
+            daily("review", Priority::Idle, 9),
+            daily("plants", Priority::Norm, 18),
+        ])
+        .unwrap();
+        let mut commits = vec![
+            Commit::completed("gym".to_string(), d(2025, 10, 13, 9, 0, 0)),
+            Commit::completed("review".to_string(), d(2025, 10, 20, 8, 0, 0)),
+        ];
+        let start = d(2025, 10, 20, 0, 0, 0);
+        let previous =
+            Planner::new(&book, &Journal::new(commits.clone())).plan(start, Duration::days(3));
+        assert_eq!(
+            vec![d(2025, 10, 20, 9, 0, 0)],
+            planned_for(&previous, "gym")
+        );
+        assert_eq!(
+            vec![d(2025, 10, 21, 9, 0, 0), d(2025, 10, 22, 9, 0, 0)],
+            planned_for(&previous, "review")
+        );
+
+        let commit = Commit::postponed(
+            "gym".to_string(),
+            d(2025, 10, 20, 8, 30, 0),
+            Postpone::Until(d(2025, 10, 21, 9, 0, 0)),
+        );
+        commits.push(commit.clone());
+        let journal = Journal::new(commits);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.replan(&previous, &commit, start, Duration::days(3));
+
+        assert_eq!(vec![d(2025, 10, 21, 9, 0, 0)], planned_for(&plan, "gym"));
+        assert_eq!(vec![d(2025, 10, 22, 9, 0, 0)], planned_for(&plan, "review"));
+        assert_eq!(
+            planned_for(&previous, "plants"),
+            planned_for(&plan, "plants")
+        );
+
+        let displaced: Vec<_> = plan
+            .displaced()
+            .iter()
+            .map(|displacement| {
+                (
+                    displacement.entry().blueprint_id(),
+                    displacement.entry().planned_for(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("review", d(2025, 10, 21, 9, 0, 0)),
+                ("review", d(2025, 10, 22, 9, 0, 0)),
+            ],
+            displaced
+        );
+    }
+
+    #[test]
+    fn test_plan_keeps_previous_entries() {
+        let book = Book::new(vec![
+            blueprint(
+                "review",
+                Priority::Norm,
+                Recurrence::Period {
+                    spacing: Duration::hours(12),
+                },
+                Slot::Hour(HourSlot::Range { start: 9, stop: 17 }),
+            ),
+            blueprint(
+                "dentist",
+                Priority::Crit,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Fixed { hour: 14 }),
+            ),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let previous = Plan::new(
+            [
+                d(2025, 10, 20, 14, 0, 0),
+                d(2025, 10, 21, 14, 0, 0),
+                d(2025, 10, 22, 14, 0, 0),
+            ]
+            .into_iter()
+            .map(|ts| PlanEntry::new("review".to_string(), Duration::hours(1), ts))
+            .collect(),
+        );
+        let sut = Planner::new(&book, &journal).with_previous(&previous);
+
+        let plan = sut.plan(d(2025, 10, 20, 0, 0, 0), Duration::days(3));
+
+        // Only the entry that the dentist took moves.
+        assert_eq!(
+            vec![
+                d(2025, 10, 20, 15, 0, 0),
+                d(2025, 10, 21, 14, 0, 0),
+                d(2025, 10, 22, 14, 0, 0),
+            ],
+            planned_for(&plan, "review")
+        );
+        assert_eq!(1, plan.moved());
+
+        // Entries are kept as long as their time is, whatever else changed.
+        let previous = Plan::new(
+            previous
+                .entries()
+                .iter()
+                .map(|entry| {
+                    PlanEntry::new(
+                        entry.blueprint_id().to_string(),
+                        Duration::minutes(30),
+                        entry.planned_for(),
+                    )
+                })
+                .collect(),
+        );
+        let sut = Planner::new(&book, &journal).with_previous(&previous);
+
+        let plan = sut.plan(d(2025, 10, 20, 0, 0, 0), Duration::days(3));
+        assert_eq!(1, plan.moved());
+    }
+
+    #[test]
+    fn test_plan_pins_occurrences() {
+        let book = Book::new(vec![
+            blueprint(
+                "dentist",
+                Priority::Norm,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            ),
+            daily("gym", Priority::Crit, 16),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let pins = Pins::new(vec![
+            Pin::new("dentist".to_string(), d(2025, 10, 24, 16, 0, 0)),
+            Pin::new("gym".to_string(), d(2025, 10, 19, 16, 0, 0)),
+            Pin::new("vet".to_string(), d(2025, 10, 24, 12, 0, 0)),
+        ]);
+        let sut = Planner::new(&book, &journal).with_pins(&pins);
+
+        let plan = sut.plan(d(2025, 10, 23, 0, 0, 0), Duration::days(2));
+
+        // The pin is kept outside of the slot, and not even a critical
+        // blueprint takes over.
+        assert_eq!(
+            vec![d(2025, 10, 24, 16, 0, 0)],
+            planned_for(&plan, "dentist")
+        );
+        assert_eq!(vec![d(2025, 10, 23, 16, 0, 0)], planned_for(&plan, "gym"));
+
+        let stale: Vec<_> = plan
+            .stale_pins()
+            .iter()
+            .map(|stale| (stale.pin().blueprint_id(), stale.staleness()))
+            .collect();
+        assert_eq!(
+            vec![("gym", Staleness::Past), ("vet", Staleness::Deleted)],
+            stale
+        );
+    }
+
+    #[test]
+    fn test_plan_pins_recurring_occurrences() {
+        let book = Book::new(vec![daily("plants", Priority::Norm, 9)]).unwrap();
+        let commit = Commit::completed("plants".to_string(), d(2025, 10, 19, 9, 0, 0));
+        let journal = Journal::new(vec![commit.clone()]);
+        let pins = Pins::new(vec![Pin::new(
+            "plants".to_string(),
+            d(2025, 10, 22, 15, 0, 0),
+        )]);
+        let start = d(2025, 10, 20, 0, 0, 0);
+        let sut = Planner::new(&book, &journal).with_pins(&pins);
+
+        let plan = sut.plan(start, Duration::days(4));
+
+        // The pin stands for the occurrence of its day only, and the
+        // sequence goes on from it.
+        assert_eq!(
+            vec![
+                d(2025, 10, 20, 9, 0, 0),
+                d(2025, 10, 21, 9, 0, 0),
+                d(2025, 10, 22, 15, 0, 0),
+            ],
+            planned_for(&plan, "plants")
+        );
+
+        let sut = sut.with_previous(&plan);
+        let again = sut.plan(start, Duration::days(4));
+        assert_eq!(plan.entries(), again.entries());
+        assert_eq!(0, again.moved());
+
+        let replanned = sut.replan(&plan, &commit, start, Duration::days(4));
+        assert_eq!(plan.entries(), replanned.entries());
+    }
+
+    #[test]
+    fn test_plan_reports_blocked_pins() {
+        let book = Book::new(vec![daily("plants", Priority::Norm, 9)])
+            .unwrap()
+            .with_blackout(Blackout::new(
+                "trip".to_string(),
+                d(2025, 10, 21, 0, 0, 0),
+                d(2025, 10, 22, 0, 0, 0),
+            ));
+        let journal = Journal::new(vec![]);
+        let busy = vec![Busy::new(
+            "Dentist".to_string(),
+            d(2025, 10, 20, 12, 30, 0),
+            d(2025, 10, 20, 13, 30, 0),
+        )];
+        let pins = Pins::new(vec![
+            Pin::new("plants".to_string(), d(2025, 10, 20, 12, 0, 0)),
+            Pin::new("plants".to_string(), d(2025, 10, 21, 12, 0, 0)),
+            Pin::new("plants".to_string(), d(2025, 10, 25, 12, 0, 0)),
+        ]);
+        let sut = Planner::new(&book, &journal)
+            .with_pins(&pins)
+            .with_busy(&busy);
+
+        let plan = sut.plan(d(2025, 10, 20, 0, 0, 0), Duration::days(3));
+
+        // Pins past the horizon are left for later plans.
+        assert_eq!(
+            vec![d(2025, 10, 20, 9, 0, 0), d(2025, 10, 22, 9, 0, 0)],
+            planned_for(&plan, "plants")
+        );
+        let stale: Vec<_> = plan
+            .stale_pins()
+            .iter()
+            .map(|stale| (stale.pin().pinned_for(), stale.staleness()))
+            .collect();
+        assert_eq!(
+            vec![
+                (d(2025, 10, 20, 12, 0, 0), Staleness::Busy),
+                (d(2025, 10, 21, 12, 0, 0), Staleness::Blackout),
+            ],
+            stale
+        );
+    }
+
+    #[test]
+    fn test_plan_splits_long_occurrences() {
+        let book = Book::new(vec![
+            blueprint_for(
+                "taxes",
+                Duration::hours(6),
+                Priority::Norm,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Range {
+                    start: 10,
+                    stop: 13,
+                }),
+            )
+            .with_split(Duration::hours(2)),
+        ])
+        .unwrap();
+        let chunks = |plan: &Plan| -> Vec<_> {
+            plan.entries()
+                .iter()
+                .map(|entry| (entry.planned_for(), entry.duration()))
+                .collect()
+        };
+
+        let journal = Journal::new(vec![]);
+        let plan = Planner::new(&book, &journal).plan(d(2025, 10, 23, 0, 0, 0), Duration::days(3));
+        assert_eq!(
+            vec![
+                (d(2025, 10, 23, 10, 0, 0), Duration::hours(4)),
+                (d(2025, 10, 24, 10, 0, 0), Duration::hours(2)),
+            ],
+            chunks(&plan)
+        );
+
+        // A chunk never leaves less than the minimum behind.
+        let journal = Journal::new(vec![Commit::progressed(
+            "taxes".to_string(),
+            d(2025, 10, 22, 12, 0, 0),
+            Duration::hours(1),
+        )]);
+        let plan = Planner::new(&book, &journal).plan(d(2025, 10, 23, 0, 0, 0), Duration::days(3));
+        assert_eq!(
+            vec![
+                (d(2025, 10, 23, 10, 0, 0), Duration::hours(3)),
+                (d(2025, 10, 24, 10, 0, 0), Duration::hours(2)),
+            ],
+            chunks(&plan)
+        );
+
+        // Once all the work is done, the occurrence is completed.
+        let journal = Journal::new(vec![
+            Commit::progressed(
+                "taxes".to_string(),
+                d(2025, 10, 22, 12, 0, 0),
+                Duration::hours(3),
+            ),
+            Commit::progressed(
+                "taxes".to_string(),
+                d(2025, 10, 23, 12, 0, 0),
+                Duration::hours(3),
+            ),
+        ]);
+        let plan = Planner::new(&book, &journal).plan(d(2025, 10, 24, 0, 0, 0), Duration::days(3));
+        assert_eq!(Vec::<(DateTime<Local>, Duration)>::new(), chunks(&plan));
+    }
+
+    #[test]
+    fn test_plan_keeps_buffers_free() {
+        let errands = Slot::Hour(HourSlot::Range { start: 9, stop: 17 });
+        let book = Book::new(vec![
+            blueprint("bank", Priority::Norm, Recurrence::Once, errands)
+                .with_buffers(Duration::minutes(30), Duration::minutes(30)),
+            blueprint("post", Priority::Norm, Recurrence::Once, errands),
+        ])
+        .unwrap()
+        .with_min_gap(Duration::hours(2));
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 0, 0, 0), Duration::days(1));
+
+        let entries: Vec<_> = plan
+            .entries()
+            .iter()
+            .map(|entry| {
+                (
+                    entry.blueprint_id(),
+                    entry.planned_for(),
+                    entry.buffer_before(),
+                    entry.buffer_after(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    "bank",
+                    d(2025, 10, 23, 9, 0, 0),
+                    Some(Duration::minutes(30)),
+                    Some(Duration::hours(2)),
+                ),
+                (
+                    "post",
+                    d(2025, 10, 23, 12, 0, 0),
+                    None,
+                    Some(Duration::hours(2)),
+                ),
+            ],
+            entries
+        );
+    }
+
+    #[test]
+    fn test_plan_waits_for_prerequisites() {
+        let daytime = Slot::Hour(HourSlot::Range { start: 9, stop: 17 });
+        let book = Book::new(vec![
+            blueprint("wash", Priority::High, Recurrence::Once, daytime)
+                .with_dependency(Dependency::new("kit".to_string()).with_delay(Duration::days(1))),
+            blueprint("kit", Priority::Norm, Recurrence::Once, daytime),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 0, 0, 0), Duration::days(3));
+
+        assert_eq!(vec![d(2025, 10, 23, 9, 0, 0)], planned_for(&plan, "kit"));
+        assert_eq!(vec![d(2025, 10, 24, 10, 0, 0)], planned_for(&plan, "wash"));
+
+        // Once the prerequisite is done, it no longer holds off.
+        let journal = Journal::new(vec![Commit::completed(
+            "kit".to_string(),
+            d(2025, 10, 1, 9, 0, 0),
+        )]);
+        let sut = Planner::new(&book, &journal);
+
+        let plan = sut.plan(d(2025, 10, 23, 0, 0, 0), Duration::days(3));
+
+        assert_eq!(vec![d(2025, 10, 23, 9, 0, 0)], planned_for(&plan, "wash"));
+    }
+
+    #[test]
+    fn test_plan_batches_groups() {
+        let daytime = Slot::Hour(HourSlot::Range { start: 9, stop: 17 });
+        let blueprints = vec![
+            blueprint("paint", Priority::Norm, Recurrence::Once, daytime)
+                .with_group("hardware".to_string()),
+            blueprint(
+                "screws",
+                Priority::Norm,
+                Recurrence::Period {
+                    spacing: Duration::days(7),
+                },
+                daytime,
+            )
+            .with_group("hardware".to_string()),
+        ];
+        let journal = Journal::new(vec![Commit::completed(
+            "screws".to_string(),
+            d(2025, 10, 20, 10, 0, 0),
+        )]);
+
+        let book = Book::new(blueprints.clone()).unwrap();
+        let plan = Planner::new(&book, &journal).plan(d(2025, 10, 23, 0, 0, 0), Duration::days(7));
+        assert_eq!(vec![d(2025, 10, 23, 9, 0, 0)], planned_for(&plan, "paint"));
+        assert_eq!(
+            vec![d(2025, 10, 27, 10, 0, 0)],
+            planned_for(&plan, "screws")
+        );
+
+        let book = Book::new(blueprints)
+            .unwrap()
+            .with_pull_forward(Duration::days(5));
+        let plan = Planner::new(&book, &journal).plan(d(2025, 10, 23, 0, 0, 0), Duration::days(7));
+        assert_eq!(vec![d(2025, 10, 23, 9, 0, 0)], planned_for(&plan, "paint"));
+        assert_eq!(
+            vec![d(2025, 10, 23, 10, 0, 0)],
+            planned_for(&plan, "screws")
+        );
+    }
+
+    #[test]
+    fn test_plan_keeps_anti_affinities() {
+        let daytime = Slot::Hour(HourSlot::Range { start: 9, stop: 17 });
+        let blueprints = vec![
+            blueprint("budget", Priority::High, Recurrence::Once, daytime),
+            blueprint("mop", Priority::Norm, Recurrence::Once, daytime)
+                .with_group("cleaning".to_string()),
+        ];
+        let anti_affinity = AntiAffinity::new(
+            Target::Blueprint("budget".to_string()),
+            Target::Group("cleaning".to_string()),
+            Separation::Day,
+        );
+        let journal = Journal::new(vec![]);
+        let start = d(2025, 10, 23, 0, 0, 0);
+
+        let book = Book::new(blueprints.clone())
+            .unwrap()
+            .with_anti_affinity(anti_affinity.clone());
+        let plan = Planner::new(&book, &journal).plan(start, Duration::days(7));
+        assert_eq!(vec![d(2025, 10, 23, 9, 0, 0)], planned_for(&plan, "budget"));
+        assert_eq!(vec![d(2025, 10, 24, 9, 0, 0)], planned_for(&plan, "mop"));
+
+        let book = Book::new(blueprints.clone())
+            .unwrap()
+            .with_anti_affinity(anti_affinity.clone().with_penalty(Duration::hours(12)));
+        let plan = Planner::new(&book, &journal).plan(start, Duration::days(7));
+        assert_eq!(vec![d(2025, 10, 23, 10, 0, 0)], planned_for(&plan, "mop"));
+
+        let book = Book::new(blueprints)
+            .unwrap()
+            .with_anti_affinity(anti_affinity.with_penalty(Duration::days(2)));
+        let plan = Planner::new(&book, &journal).plan(start, Duration::days(7));
+        assert_eq!(vec![d(2025, 10, 24, 9, 0, 0)], planned_for(&plan, "mop"));
+    }
+
+    #[test]
+    fn test_plan_skips_blackouts() {
+        let review = blueprint(
+            "review",
+            Priority::Norm,
+            Recurrence::Period {
+                spacing: Duration::days(7),
+            },
+            Slot::Hour(HourSlot::Range { start: 9, stop: 17 }),
+        );
+        let blackout = Blackout::new(
+            "trip".to_string(),
+            d(2025, 10, 24, 0, 0, 0),
+            d(2025, 10, 27, 0, 0, 0),
+        );
+        let journal = Journal::new(vec![Commit::completed(
+            "review".to_string(),
+            d(2025, 10, 17, 10, 0, 0),
+        )]);
+        let plan = |review: Blueprint, blackout: Blackout| {
+            let book = Book::new(vec![review]).unwrap().with_blackout(blackout);
+            let plan =
+                Planner::new(&book, &journal).plan(d(2025, 10, 23, 0, 0, 0), Duration::days(14));
+            planned_for(&plan, "review")
+        };
+
+        // Caught up with right after the blackout.
+        assert_eq!(
+            vec![d(2025, 10, 27, 9, 0, 0), d(2025, 11, 3, 9, 0, 0)],
+            plan(review.clone(), blackout.clone())
+        );
+
+        // Skipped until the next regular occurrence.
+        assert_eq!(
+            vec![d(2025, 10, 31, 10, 0, 0)],
+            plan(
+                review.clone().with_catch_up(CatchUp::Skip),
+                blackout.clone()
+            )
+        );
+
+        // Travel-safe blueprints are not blocked by blackouts that allow them.
+        assert_eq!(
+            vec![d(2025, 10, 27, 9, 0, 0), d(2025, 11, 3, 9, 0, 0)],
+            plan(review.clone().with_travel_safe(true), blackout.clone())
+        );
+        assert_eq!(
+            vec![d(2025, 10, 24, 10, 0, 0), d(2025, 10, 31, 10, 0, 0)],
+            plan(review.with_travel_safe(true), blackout.with_travel_safe())
+        );
+    }
+
+    #[test]
+    fn test_plan_keeps_off_busy_time() {
+        let book = Book::new(vec![
+            blueprint(
+                "focus",
+                Priority::Crit,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Range { start: 9, stop: 17 }),
+            ),
+            daily("standup", Priority::Norm, 9),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let busy = vec![
+            Busy::new(
+                "Planning".to_string(),
+                d(2025, 10, 23, 9, 0, 0),
+                d(2025, 10, 23, 10, 30, 0),
+            ),
+            Busy::new(
+                "Review".to_string(),
+                d(2025, 10, 23, 10, 0, 0),
+                d(2025, 10, 23, 11, 0, 0),
+            ),
+        ];
+        let plan = Planner::new(&book, &journal)
+            .with_busy(&busy)
+            .plan(d(2025, 10, 23, 0, 0, 0), Duration::days(2));
+
+        assert_eq!(vec![d(2025, 10, 23, 11, 0, 0)], planned_for(&plan, "focus"));
+        assert_eq!(
+            vec![d(2025, 10, 24, 9, 0, 0)],
+            planned_for(&plan, "standup")
+        );
+    }
+
+    #[test]
+    fn test_plan_explains_decisions() {
+        let book = Book::new(vec![
+            blueprint(
+                "focus",
+                Priority::Crit,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Range { start: 9, stop: 17 }),
+            ),
+            daily("standup", Priority::Norm, 9),
+            daily("gym", Priority::Norm, 18).with_paused(true),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let busy = vec![Busy::new(
+            "Planning".to_string(),
+            d(2025, 10, 23, 9, 0, 0),
+            d(2025, 10, 23, 11, 0, 0),
+        )];
+        let plan = Planner::new(&book, &journal)
+            .with_busy(&busy)
+            .with_explanations()
+            .plan(d(2025, 10, 23, 0, 0, 0), Duration::days(2));
+
+        assert_eq!(
+            Some(&Explanation::new(
+                "focus".to_string(),
+                vec![
+                    Attempt::rejected(d(2025, 10, 23, 9, 0, 0), Rejection::Busy),
+                    Attempt::rejected(d(2025, 10, 23, 10, 0, 0), Rejection::Busy),
+                    Attempt::placed(d(2025, 10, 23, 11, 0, 0)),
+                ],
+                Decision::Planned(1),
+            )),
+            plan.explanation_for("focus")
+        );
+        assert_eq!(
+            Some(&Explanation::new(
+                "standup".to_string(),
+                vec![
+                    Attempt::rejected(d(2025, 10, 23, 9, 0, 0), Rejection::Busy),
+                    Attempt::placed(d(2025, 10, 24, 9, 0, 0)),
+                ],
+                Decision::Planned(1),
+            )),
+            plan.explanation_for("standup")
+        );
+        assert_eq!(
+            Some(&Decision::Paused),
+            plan.explanation_for("gym").map(Explanation::decision)
+        );
+        assert!(
+            Planner::new(&book, &journal)
+                .plan(d(2025, 10, 23, 0, 0, 0), Duration::days(2))
+                .explanations()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_plan_levels_weeks() {
+        let quarterly = |id: &str| {
+            blueprint(
+                id,
+                Priority::Norm,
+                Recurrence::Period {
+                    spacing: Duration::of(3, TimeUnit::Month),
+                },
+                Slot::Hour(HourSlot::Fixed { hour: 9 }),
+            )
+        };
+        let book = || {
+            Book::new(vec![
+                quarterly("gutters"),
+                quarterly("filters"),
+                quarterly("smoke"),
+            ])
+            .unwrap()
+        };
+        let journal = Journal::new(vec![]);
+        // Monday.
+        let start = d(2025, 10, 20, 0, 0, 0);
+
+        let plan = Planner::new(&book(), &journal).plan(start, Duration::days(21));
+        assert_eq!(
+            vec![
+                d(2025, 10, 20, 9, 0, 0),
+                d(2025, 10, 21, 9, 0, 0),
+                d(2025, 10, 22, 9, 0, 0),
+            ],
+            plan.entries()
+                .iter()
+                .map(PlanEntry::planned_for)
+                .collect::<Vec<_>>()
+        );
+
+        let book = book().with_leveling(Duration::days(14));
+        let plan = Planner::new(&book, &journal).plan(start, Duration::days(21));
+        assert_eq!(
+            vec![
+                d(2025, 10, 20, 9, 0, 0),
+                d(2025, 10, 27, 9, 0, 0),
+                d(2025, 11, 3, 9, 0, 0),
+            ],
+            plan.entries()
+                .iter()
+                .map(PlanEntry::planned_for)
+                .collect::<Vec<_>>()
+        );
+    }
+}
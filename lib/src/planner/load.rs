@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::IsoWeek;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::TimeDelta;
+
+use crate::planner::timeline::Span;
+use crate::types::experimental::capacity::Capacity;
+
+/// Accumulates the planned work per day and per week.
+///
+/// Work is accounted on the day, and the week, where it starts.
+#[derive(Debug, Default)]
+pub struct Load {
+    days: HashMap<NaiveDate, TimeDelta>,
+    weeks: HashMap<IsoWeek, TimeDelta>,
+}
+
+impl Load {
+    pub fn add(&mut self, ts: DateTime<Local>, work: TimeDelta) {
+        *self.days.entry(ts.date_naive()).or_default() += work;
+        *self.weeks.entry(ts.iso_week()).or_default() += work;
+    }
+
+    pub fn sub(&mut self, ts: DateTime<Local>, work: TimeDelta) {
+        self.add(ts, -work);
+    }
+
+    pub fn day(&self, ts: DateTime<Local>) -> TimeDelta {
+        self.days.get(&ts.date_naive()).copied().unwrap_or_default()
+    }
+
+    pub fn week(&self, ts: DateTime<Local>) -> TimeDelta {
+        self.weeks.get(&ts.iso_week()).copied().unwrap_or_default()
+    }
+
+    /// Returns true if `span` fits within `capacity`, once the work of the
+    /// `released` spans is no longer accounted.
+    pub fn admits(&self, capacity: &Capacity, span: &Span, released: &[Span]) -> bool {
+        let ts = span.start;
+        let freed_day: TimeDelta = released
+            .iter()
+            .filter(|r| r.start.date_naive() == ts.date_naive())
+            .map(Span::length)
+            .sum();
+        let freed_week: TimeDelta = released
+            .iter()
+            .filter(|r| r.start.iso_week() == ts.iso_week())
+            .map(Span::length)
+            .sum();
+
+        let day_fits = capacity
+            .daily_limit(ts.weekday().into())
+            .is_none_or(|limit| self.day(ts) - freed_day + span.length() <= limit.timedelta());
+        let week_fits = capacity
+            .weekly_limit()
+            .is_none_or(|limit| self.week(ts) - freed_week + span.length() <= limit.timedelta());
+        day_fits && week_fits
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::test::d;
+    use crate::types::Duration;
+
+    fn span(day: u32, hours: i64) -> Span {
+        let start = d(2025, 10, day, 10, 0, 0);
+        Span::new(start, start + TimeDelta::hours(hours))
+    }
+
+    #[test]
+    fn test_admits() {
+        let capacity = Capacity::unlimited()
+            .with_daily(Duration::hours(2))
+            .with_weekly(Duration::hours(3));
+        let mut sut = Load::default();
+
+        // Thursday.
+        assert!(sut.admits(&capacity, &span(23, 2), &[]));
+        assert!(!sut.admits(&capacity, &span(23, 3), &[]));
+
+        sut.add(span(23, 2).start, TimeDelta::hours(2));
+        assert!(!sut.admits(&capacity, &span(23, 1), &[]));
+        assert!(sut.admits(&capacity, &span(23, 1), &[span(23, 2)]));
+
+        // Friday, same week.
+        assert!(sut.admits(&capacity, &span(24, 1), &[]));
+        assert!(!sut.admits(&capacity, &span(24, 2), &[]));
+
+        // Monday, next week.
+        assert!(sut.admits(&capacity, &span(27, 2), &[]));
+
+        sut.sub(span(23, 2).start, TimeDelta::hours(2));
+        assert_eq!(TimeDelta::zero(), sut.week(span(23, 2).start));
+    }
+}
@@ -0,0 +1,723 @@
+use std::cmp;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::TimeDelta;
+
+use crate::planner::ceil_hour;
+use crate::planner::load::Load;
+use crate::planner::timeline::Span;
+use crate::planner::timeline::Timeline;
+use crate::planner::track::Track;
+use crate::planner::track::padded_span_of;
+use crate::planner::track::span_of;
+use crate::types::experimental::anti_affinity::AntiAffinity;
+use crate::types::experimental::anti_affinity::Enforcement;
+use crate::types::experimental::anti_affinity::Separation;
+use crate::types::experimental::blackout::Blackout;
+use crate::types::experimental::busy::Busy;
+use crate::types::experimental::capacity::Capacity;
+use crate::types::experimental::explanation::Attempt;
+use crate::types::experimental::explanation::Decision;
+use crate::types::experimental::explanation::Explanation;
+use crate::types::experimental::explanation::Rejection;
+use crate::types::experimental::plan::Displacement;
+use crate::types::experimental::plan::Plan;
+use crate::types::experimental::plan::Reason;
+
+/// The state of a plan while it is being built.
+///
+/// Tracks are kept in priority order, and are identified by their index.
+///
+/// The timeline holds the spans taken by entries along with their buffers,
+/// keyed by the track that placed them and the span of the entry itself.
+///
+/// When explained, the candidates each track considered are recorded, along
+/// with what blocked them.
+pub struct Schedule {
+    tracks: Vec<Track>,
+    timeline: Timeline<(usize, Span)>,
+    capacity: Capacity,
+    load: Load,
+    displaced: Vec<Displacement>,
+    prerequisites: Vec<Vec<(usize, TimeDelta)>>,
+    pull_forward: TimeDelta,
+    leveling: Option<TimeDelta>,
+    apart: Vec<Vec<(usize, Separation, Enforcement)>>,
+    blackouts: Vec<Vec<Span>>,
+    busy: Timeline<()>,
+    attempts: Option<Vec<Vec<Attempt>>>,
+}
+
+/// What keeps the next entry of a track from a span, where tracks are
+/// identified by their index.
+enum Blocker {
+    Slot,
+    Pin,
+    Prerequisite(usize),
+    AntiAffinity(usize),
+    Blackout,
+    Busy,
+    Conflict(usize),
+    Capacity,
+}
+
+impl Schedule {
+    /// Creates a schedule where the entries already placed by `tracks` are
+    /// taken.
+    pub fn new(tracks: Vec<Track>, capacity: Capacity) -> Self {
+        let mut timeline = Timeline::default();
+        let mut load = Load::default();
+        for (idx, track) in tracks.iter().enumerate() {
+            for entry in track.entries() {
+                let span = span_of(entry);
+                timeline.insert(padded_span_of(entry), (idx, span));
+                load.add(span.start, span.length());
+            }
+            for span in track.pending_pins() {
+                timeline.insert(track.padded(span), (idx, span));
+                load.add(span.start, span.length());
+            }
+        }
+        // Dependencies on blueprints without a track hold nothing back; those
+        // that are not in the book at all are reported by `Book::validate`.
+        let prerequisites = tracks
+            .iter()
+            .map(|track| {
+                track
+                    .blueprint()
+                    .dependencies()
+                    .iter()
+                    .filter_map(|dependency| {
+                        let idx = tracks.iter().position(|other| {
+                            other.blueprint().id() == dependency.blueprint_id()
+                        })?;
+                        let delay = dependency
+                            .delay()
+                            .map_or_else(TimeDelta::zero, |delay| delay.timedelta());
+                        Some((idx, delay))
+                    })
+                    .collect()
+            })
+            .collect();
+        let tracks_len = tracks.len();
+        Self {
+            tracks,
+            timeline,
+            capacity,
+            load,
+            displaced: Vec::new(),
+            prerequisites,
+            pull_forward: TimeDelta::zero(),
+            leveling: None,
+            apart: vec![Vec::new(); tracks_len],
+            blackouts: vec![Vec::new(); tracks_len],
+            busy: Timeline::default(),
+            attempts: None,
+        }
+    }
+
+    /// Records the candidates considered by each track, so that the plan
+    /// can be explained.
+    pub fn with_explanations(mut self) -> Self {
+        self.attempts = Some(vec![Vec::new(); self.tracks.len()]);
+        self
+    }
+
+    /// Keeps the entries, along with their buffers, off the `busy` time.
+    pub fn with_busy(mut self, busy: &[Busy]) -> Self {
+        let mut spans: Vec<_> = busy
+            .iter()
+            .map(|busy| Span::new(busy.start(), busy.end()))
+            .filter(|span| span.start < span.end)
+            .collect();
+        spans.sort_by_key(|span| span.start);
+
+        // Busy time may overlap, while the spans of a timeline may not.
+        let mut merged: Vec<Span> = Vec::new();
+        for span in spans {
+            match merged.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => merged.push(span),
+            }
+        }
+        for span in merged {
+            self.busy.insert(span, ());
+        }
+        self
+    }
+
+    /// Keeps the occurrences of each track out of the `blackouts` that block
+    /// it.
+    pub fn with_blackouts(mut self, blackouts: &[Blackout]) -> Self {
+        self.blackouts = self
+            .tracks
+            .iter()
+            .map(|track| {
+                blackouts
+                    .iter()
+                    .filter(|blackout| blackout.blocks(track.blueprint()))
+                    .map(|blackout| Span::new(blackout.start(), blackout.end()))
+                    .collect()
+            })
+            .collect();
+        self
+    }
+
+    /// Keeps the occurrences of the tracks apart, as set by
+    /// `anti_affinities`.
+    pub fn with_anti_affinities(mut self, anti_affinities: &[AntiAffinity]) -> Self {
+        self.apart = self
+            .tracks
+            .iter()
+            .enumerate()
+            .map(|(idx, track)| {
+                anti_affinities
+                    .iter()
+                    .flat_map(|anti_affinity| {
+                        self.tracks
+                            .iter()
+                            .enumerate()
+                            .filter(move |(other, other_track)| {
+                                *other != idx
+                                    && anti_affinity
+                                        .applies(track.blueprint(), other_track.blueprint())
+                            })
+                            .map(|(other, _)| {
+                                (
+                                    other,
+                                    anti_affinity.separation(),
+                                    anti_affinity.enforcement(),
+                                )
+                            })
+                    })
+                    .collect()
+            })
+            .collect();
+        self
+    }
+
+    /// Lets occurrences be planned up to `pull_forward` early, so that they
+    /// are batched along with others of the same group.
+    pub fn with_pull_forward(mut self, pull_forward: TimeDelta) -> Self {
+        self.pull_forward = pull_forward;
+        self
+    }
+
+    /// Evens out the work across weeks, when `tolerance` is set, by letting
+    /// occurrences go up to that much later, see [level](Schedule::level).
+    pub const fn with_leveling(mut self, tolerance: Option<TimeDelta>) -> Self {
+        self.leveling = tolerance;
+        self
+    }
+
+    /// Carries over displacements from a previous plan.
+    pub fn with_displaced(mut self, displaced: Vec<Displacement>) -> Self {
+        self.displaced = displaced;
+        self
+    }
+
+    /// Returns the order in which tracks get to place their occurrences:
+    /// by priority, and then by earliest deadline first. Frozen tracks do not
+    /// place occurrences.
+    pub fn order(&self) -> Vec<usize> {
+        let mut order: Vec<_> = (0..self.tracks.len())
+            .filter(|idx| !self.tracks[*idx].is_frozen())
+            .collect();
+        order.sort_by_key(|idx| {
+            let track = &self.tracks[*idx];
+            let due = track.sequencer().due();
+            (cmp::Reverse(track.priority()), due.is_none(), due)
+        });
+        order
+    }
+
+    /// Returns the earliest timestamp at or after `from` where any track may
+    /// place its next occurrence.
+    ///
+    /// Candidates within a blackout are deferred to its end, where the track
+    /// catches up with what it missed.
+    pub fn next_candidate(&self, from: DateTime<Local>) -> Option<DateTime<Local>> {
+        (0..self.tracks.len())
+            .filter(|idx| !self.tracks[*idx].is_frozen())
+            .filter_map(|idx| {
+                let track = &self.tracks[idx];
+                let pin = track.pending_pins().map(|span| span.start.max(from)).next();
+                let candidate = track.sequencer().next_candidate(from).map(|candidate| {
+                    self.blackouts[idx]
+                        .iter()
+                        .find(|blackout| blackout.start <= candidate && candidate < blackout.end)
+                        .map_or(candidate, |blackout| blackout.end)
+                });
+                candidate.into_iter().chain(pin).min()
+            })
+            .min()
+    }
+
+    /// Tries to place the next occurrence of the track `idx`, now that the
+    /// sweep reached `ts`.
+    ///
+    /// The occurrence goes at `ts`, unless its recurrence prefers some other
+    /// timestamp within its window. Entries of lower priority blueprints
+    /// that are in the way are preempted, unless they are pinned, and their
+    /// tracks rolled back so that they get planned again later on, as well
+    /// as the tracks that depend on them. The occurrence is not placed when
+    /// it does not fit within the capacity, so it spills over to a later
+    /// window.
+    ///
+    /// Returns true if the occurrence was placed.
+    pub fn try_place(&mut self, idx: usize, ts: DateTime<Local>) -> bool {
+        self.tracks[idx].commit_pins(ts);
+        for blackout in &self.blackouts[idx] {
+            if blackout.end <= ts {
+                self.tracks[idx].resume(*blackout);
+            }
+        }
+
+        let Some((span, victims)) = self
+            .choose(idx, ts)
+            .and_then(|ts| self.fit(idx, ts, TimeDelta::zero()))
+        else {
+            self.reject(idx, ts);
+            return false;
+        };
+
+        let track = &self.tracks[idx];
+        let reason = Reason::Preempted {
+            by: track.blueprint().id().to_string(),
+            priority: track.priority(),
+        };
+        for (victim, owner) in victims {
+            self.release(owner, victim.start, &reason);
+        }
+
+        self.tracks[idx].place(span);
+        self.timeline
+            .insert(self.tracks[idx].padded(span), (idx, span));
+        self.load.add(span.start, span.length());
+        self.record(idx, Attempt::placed(span.start));
+        self.batch(idx, span);
+        true
+    }
+
+    /// Records why the next occurrence of the track `idx` could not be
+    /// placed at `ts`, if its sequence accepts `ts` at all.
+    fn reject(&mut self, idx: usize, ts: DateTime<Local>) {
+        let track = &self.tracks[idx];
+        if self.attempts.is_none() || !track.sequencer().accepts(ts) {
+            return;
+        }
+        let Some(length) = track.lengths().last().copied() else {
+            return;
+        };
+        let Err(blocker) = self.check(idx, &Span::new(ts, ts + length), TimeDelta::zero()) else {
+            return;
+        };
+        let id = |idx: usize| self.tracks[idx].blueprint().id().to_string();
+        let rejection = match blocker {
+            Blocker::Slot => Rejection::Slot,
+            Blocker::Pin => Rejection::Pin,
+            Blocker::Prerequisite(other) => Rejection::Prerequisite(id(other)),
+            Blocker::AntiAffinity(other) => Rejection::AntiAffinity(id(other)),
+            Blocker::Blackout => Rejection::Blackout,
+            Blocker::Busy => Rejection::Busy,
+            Blocker::Conflict(other) => Rejection::Conflict(id(other)),
+            Blocker::Capacity => Rejection::Capacity,
+        };
+        self.record(idx, Attempt::rejected(ts, rejection));
+    }
+
+    fn record(&mut self, idx: usize, attempt: Attempt) {
+        if let Some(attempts) = self.attempts.as_mut() {
+            attempts[idx].push(attempt);
+        }
+    }
+
+    /// Batches the tracks that share a group with the track `idx` right
+    /// after its entry at `span`, one after the other, so that they are done
+    /// in one go.
+    ///
+    /// Only occurrences that may take place by then, or at most the
+    /// pull-forward tolerance later, join the batch. Batching never
+    /// preempts other entries.
+    fn batch(&mut self, idx: usize, span: Span) {
+        let mut batched = vec![idx];
+        let mut end = self.tracks[idx].padded(span).end;
+        loop {
+            let leader = self.tracks[idx].blueprint();
+            let Some((mate, span)) = self
+                .tracks
+                .iter()
+                .enumerate()
+                .filter(|(mate, track)| {
+                    !batched.contains(mate)
+                        && !track.is_frozen()
+                        && track.blueprint().shares_group(leader)
+                })
+                .find_map(|(mate, track)| {
+                    let start = end + track.buffer_before();
+                    track
+                        .sequencer()
+                        .next_candidate(start)
+                        .filter(|candidate| *candidate - start <= self.pull_forward)?;
+                    self.fit(mate, start, self.pull_forward)
+                        .filter(|(_, victims)| victims.is_empty())
+                        .map(|(span, _)| (mate, span))
+                })
+            else {
+                return;
+            };
+
+            self.tracks[mate].pull(span, self.pull_forward);
+            self.timeline
+                .insert(self.tracks[mate].padded(span), (mate, span));
+            self.load.add(span.start, span.length());
+            self.record(mate, Attempt::placed(span.start));
+            batched.push(mate);
+            end = self.tracks[mate].padded(span).end;
+        }
+    }
+
+    /// Rolls back the track `owner` from `from` on, along with the tracks
+    /// that depend on it, and reports the removed entries as displaced.
+    fn release(&mut self, owner: usize, from: DateTime<Local>, reason: &Reason) {
+        let mut pending = vec![owner];
+        while let Some(owner) = pending.pop() {
+            let entries = self.tracks[owner].rollback(from);
+            if entries.is_empty() {
+                continue;
+            }
+            for entry in entries {
+                let span = span_of(&entry);
+                self.timeline.remove(&padded_span_of(&entry), (owner, span));
+                self.load.sub(span.start, span.length());
+                self.displaced
+                    .push(Displacement::new(entry, reason.clone()));
+            }
+            pending.extend(
+                (0..self.tracks.len())
+                    .filter(|idx| self.prerequisites[*idx].iter().any(|(p, _)| *p == owner)),
+            );
+        }
+    }
+
+    /// Returns the first prerequisite of the track `idx` that is not done by
+    /// `ts`: it has an occurrence pending, or the delay after its last
+    /// completion did not elapse yet.
+    fn waiting_for(&self, idx: usize, ts: DateTime<Local>) -> Option<usize> {
+        self.prerequisites[idx]
+            .iter()
+            .find(|(prerequisite, delay)| {
+                let prerequisite = &self.tracks[*prerequisite];
+                prerequisite.sequencer().is_pending_at(ts)
+                    || prerequisite
+                        .completed_at()
+                        .is_some_and(|completed_at| ts < completed_at + *delay)
+            })
+            .map(|(prerequisite, _)| *prerequisite)
+    }
+
+    /// Chooses where the next occurrence of the track `idx` goes, at or
+    /// after `ts`.
+    ///
+    /// The time at which the occurrence was planned previously is kept, as
+    /// long as it is still valid. Without a preference, that is `ts` itself.
+    /// Otherwise, it is the candidate within the window of the recurrence
+    /// that is closest to the preferred timestamp.
+    ///
+    /// Candidates that violate soft anti-affinities are penalized, as if
+    /// they were that much further away, so later candidates may win.
+    ///
+    /// When leveling, the week with the least work goes first, see
+    /// [level](Schedule::level).
+    fn choose(&self, idx: usize, ts: DateTime<Local>) -> Option<DateTime<Local>> {
+        if let Some(kept) = self.keep(idx, ts) {
+            return Some(kept);
+        }
+        if let Some(tolerance) = self.leveling {
+            return self.level(idx, ts, tolerance);
+        }
+
+        let sequencer = self.tracks[idx].sequencer();
+        let target = match sequencer.preferred() {
+            Some(preferred) => preferred,
+            None => {
+                self.fit(idx, ts, TimeDelta::zero())?;
+                if self.penalty(idx, ts).is_zero() {
+                    return Some(ts);
+                }
+                ts
+            }
+        };
+
+        let mut best: Option<(DateTime<Local>, TimeDelta)> = None;
+        let mut from = ts;
+        while let Some(candidate) = sequencer.next_candidate(from).map(ceil_hour) {
+            if let Some((_, score)) = best
+                && candidate >= target
+                && candidate - target >= score
+            {
+                break;
+            }
+            if self.fit(idx, candidate, TimeDelta::zero()).is_some() {
+                let score = (candidate - target).abs() + self.penalty(idx, candidate);
+                if best.is_none_or(|(_, best)| score < best) {
+                    best = Some((candidate, score));
+                }
+            }
+            from = candidate + TimeDelta::hours(1);
+        }
+        best.map(|(candidate, _)| candidate)
+    }
+
+    /// Chooses where the next occurrence of the track `idx` goes, among the
+    /// candidates up to `tolerance` after `ts`: in the week with the least
+    /// work planned so far, and within it, as [choose](Schedule::choose)
+    /// does.
+    ///
+    /// Only the tracks that may place their occurrence at `ts` get to
+    /// choose, so that the tolerance counts from when the occurrence may
+    /// first take place.
+    fn level(
+        &self,
+        idx: usize,
+        ts: DateTime<Local>,
+        tolerance: TimeDelta,
+    ) -> Option<DateTime<Local>> {
+        let sequencer = self.tracks[idx].sequencer();
+        if !sequencer.accepts(ts) {
+            return None;
+        }
+
+        let target = sequencer.preferred().unwrap_or(ts);
+        let mut best: Option<(DateTime<Local>, (TimeDelta, TimeDelta))> = None;
+        let mut from = ts;
+        while let Some(candidate) = sequencer.next_candidate(from).map(ceil_hour)
+            && candidate <= ts + tolerance
+        {
+            // Nothing beats an empty week, other than being closer.
+            if let Some((_, (work, score))) = best
+                && work.is_zero()
+                && candidate >= target
+                && candidate - target >= score
+            {
+                break;
+            }
+            if self.fit(idx, candidate, TimeDelta::zero()).is_some() {
+                let score = (
+                    self.load.week(candidate),
+                    (candidate - target).abs() + self.penalty(idx, candidate),
+                );
+                if best.is_none_or(|(_, best)| score < best) {
+                    best = Some((candidate, score));
+                }
+            }
+            from = candidate + TimeDelta::hours(1);
+        }
+        best.map(|(candidate, _)| candidate)
+    }
+
+    /// Returns the first track whose entries an occurrence of the track
+    /// `idx` at `ts` is not kept apart from, as hard anti-affinities say.
+    fn too_close_to(&self, idx: usize, ts: DateTime<Local>) -> Option<usize> {
+        self.apart[idx]
+            .iter()
+            .filter(|(_, _, enforcement)| *enforcement == Enforcement::Hard)
+            .find(|(other, separation, _)| !self.separates(*other, *separation, ts))
+            .map(|(other, _, _)| *other)
+    }
+
+    /// Returns the penalty of the soft anti-affinities that an occurrence of
+    /// the track `idx` at `ts` violates.
+    fn penalty(&self, idx: usize, ts: DateTime<Local>) -> TimeDelta {
+        self.apart[idx]
+            .iter()
+            .filter_map(|(other, separation, enforcement)| match enforcement {
+                Enforcement::Soft(penalty) if !self.separates(*other, *separation, ts) => {
+                    Some(penalty.timedelta())
+                }
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Returns true if every entry of the track `other` is separated from
+    /// `ts`.
+    fn separates(&self, other: usize, separation: Separation, ts: DateTime<Local>) -> bool {
+        self.tracks[other]
+            .entries()
+            .all(|entry| separation.separates(ts, entry.planned_for()))
+    }
+
+    /// Returns the time at which the next occurrence of the track `idx` was
+    /// planned previously, if it is at or after `ts` and still valid.
+    fn keep(&self, idx: usize, ts: DateTime<Local>) -> Option<DateTime<Local>> {
+        let kept = self.tracks[idx]
+            .previous()
+            .filter(|previous| *previous >= ts)?;
+        self.fit(idx, kept, TimeDelta::zero()).map(|_| kept)
+    }
+
+    /// Returns the longest span the next entry of the track `idx` may take
+    /// at `ts`, up to `early` before its spacing allows, along with the
+    /// entries that must be preempted for it, or `None` if it cannot be
+    /// placed there.
+    ///
+    /// Occurrences of splittable blueprints that do not fit in one go take
+    /// the longest chunk that fits, and the rest goes to later windows.
+    fn fit(
+        &self,
+        idx: usize,
+        ts: DateTime<Local>,
+        early: TimeDelta,
+    ) -> Option<(Span, Vec<(Span, usize)>)> {
+        self.tracks[idx].lengths().into_iter().find_map(|length| {
+            let span = Span::new(ts, ts + length);
+            self.check(idx, &span, early)
+                .ok()
+                .map(|victims| (span, victims))
+        })
+    }
+
+    /// Returns the entries that must be preempted to place the next entry of
+    /// the track `idx` at `span`, up to `early` before its spacing allows, or
+    /// what blocks it if it cannot be placed there.
+    fn check(
+        &self,
+        idx: usize,
+        span: &Span,
+        early: TimeDelta,
+    ) -> Result<Vec<(Span, usize)>, Blocker> {
+        // The entry must fit entirely in the slot of its blueprint, once its
+        // spacing allows, or `early` before when batched with its group.
+        let track = &self.tracks[idx];
+        let accepts = if early.is_zero() {
+            track
+                .sequencer()
+                .accepts_for(span.start, span.length().into())
+        } else {
+            track
+                .sequencer()
+                .pulled_forward(early)
+                .accepts_for(span.start, span.length().into())
+        };
+        if !accepts {
+            return Err(Blocker::Slot);
+        }
+        // Pins stand for the occurrence that is pending by their time, which
+        // is not planned again.
+        if track.yields_to_pin(span.start) {
+            return Err(Blocker::Pin);
+        }
+        // Prerequisites must have no occurrence pending, and the delay after
+        // their last completion must have elapsed.
+        if let Some(prerequisite) = self.waiting_for(idx, span.start) {
+            return Err(Blocker::Prerequisite(prerequisite));
+        }
+        // Hard anti-affinities always keep apart; soft ones are weighed in
+        // `choose` instead.
+        if let Some(other) = self.too_close_to(idx, span.start) {
+            return Err(Blocker::AntiAffinity(other));
+        }
+        // Occurrences that come due during a blackout are caught up with
+        // once it is over.
+        if self.blackouts[idx]
+            .iter()
+            .any(|blackout| blackout.overlaps(span))
+        {
+            return Err(Blocker::Blackout);
+        }
+        // The buffers of the blueprint, and the minimum gap of the book, are
+        // kept free of busy time and of other entries alike.
+        if !self.busy.is_free(&track.padded(*span)) {
+            return Err(Blocker::Busy);
+        }
+
+        // Entries in the way are preempted, unless they are pinned or their
+        // priority, escalated by postponing, is at least as high.
+        let victims: Vec<_> = self
+            .timeline
+            .overlapping(&track.padded(*span))
+            .map(|(_, (owner, taken))| (*taken, *owner))
+            .collect();
+        if let Some((_, owner)) = victims.iter().find(|(taken, owner)| {
+            let owner = &self.tracks[*owner];
+            owner.is_pinned_at(taken.start) || owner.priority_at(taken.start) >= track.priority()
+        }) {
+            return Err(Blocker::Conflict(*owner));
+        }
+
+        // Work that would exceed the daily or weekly budget spills over to
+        // the next window with room for it.
+        let released: Vec<_> = victims.iter().map(|(victim, _)| *victim).collect();
+        if !self.load.admits(&self.capacity, span, &released) {
+            return Err(Blocker::Capacity);
+        }
+
+        Ok(victims)
+    }
+
+    /// Returns the explanation of each track, when explained, for a plan
+    /// over `[start, end)`.
+    pub fn explanations(&self, start: DateTime<Local>, end: DateTime<Local>) -> Vec<Explanation> {
+        let Some(attempts) = &self.attempts else {
+            return Vec::new();
+        };
+        self.tracks
+            .iter()
+            .zip(attempts)
+            .map(|(track, attempts)| {
+                let blueprint = track.blueprint();
+                let planned = track.entries().count();
+                let decision = if planned > 0 {
+                    Decision::Planned(planned)
+                } else if blueprint.is_paused() {
+                    Decision::Paused
+                } else if track.sequencer().is_exhausted() {
+                    Decision::Exhausted
+                } else if !attempts.is_empty() {
+                    Decision::Rejected
+                } else {
+                    match track.sequencer().next_candidate(start) {
+                        None if blueprint.active_until().is_some_and(|until| until <= end) => {
+                            Decision::Inactive
+                        }
+                        None => Decision::Closed,
+                        Some(ts) if ts >= end => Decision::NotDue(ts),
+                        Some(_) => Decision::Rejected,
+                    }
+                };
+                Explanation::new(blueprint.id().to_string(), attempts.clone(), decision)
+            })
+            .collect()
+    }
+
+    /// Places the pins that the sweep did not reach by `end`.
+    pub fn commit_pins(&mut self, end: DateTime<Local>) {
+        for track in &mut self.tracks {
+            track.commit_pins(end);
+        }
+    }
+
+    /// Returns the resulting plan, with entries ordered by time and then by
+    /// priority. Deadlines up to `end` that are not met are reported, as
+    /// well as the overdue occurrences.
+    pub fn into_plan(self, end: DateTime<Local>) -> Plan {
+        let at_risk = self
+            .tracks
+            .iter()
+            .flat_map(|track| track.at_risk(end))
+            .collect();
+        let overdue = self.tracks.iter().filter_map(Track::overdue).collect();
+        let mut entries: Vec<_> = self
+            .tracks
+            .into_iter()
+            .flat_map(Track::into_entries)
+            .collect();
+        entries.sort_by_key(|entry| entry.planned_for());
+        Plan::new(entries)
+            .with_displaced(self.displaced)
+            .with_at_risk(at_risk)
+            .with_overdue(overdue)
+    }
+}
@@ -0,0 +1,147 @@
+use chrono::DateTime;
+use chrono::Local;
+use chrono::TimeDelta;
+
+/// A half-open interval of time `[start, end)`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+impl Span {
+    pub const fn new(start: DateTime<Local>, end: DateTime<Local>) -> Self {
+        Self { start, end }
+    }
+
+    pub fn length(&self) -> TimeDelta {
+        self.end - self.start
+    }
+
+    /// Returns true if both spans share any instant.
+    pub fn overlaps(&self, other: &Span) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+/// Keeps track of the spans of time that are already taken, and who took
+/// them.
+///
+/// Spans never overlap each other, so ordering them by `start` also orders
+/// them by `end`.
+#[derive(Debug)]
+pub struct Timeline<K> {
+    spans: Vec<(Span, K)>,
+}
+
+impl<K: Copy + PartialEq> Timeline<K> {
+    /// Returns true if `span` does not overlap any taken span.
+    pub fn is_free(&self, span: &Span) -> bool {
+        self.overlapping(span).next().is_none()
+    }
+
+    /// Returns the taken spans that overlap `span`, ordered by time.
+    pub fn overlapping(&self, span: &Span) -> impl Iterator<Item = &(Span, K)> {
+        let idx = self
+            .spans
+            .partition_point(|(taken, _)| taken.end <= span.start);
+        self.spans[idx..]
+            .iter()
+            .take_while(|(taken, _)| taken.overlaps(span))
+    }
+
+    /// Marks `span` as taken by `key`.
+    pub fn insert(&mut self, span: Span, key: K) {
+        debug_assert!(
+            self.is_free(&span),
+            "always guard `insert()` with `is_free()`"
+        );
+
+        let idx = self
+            .spans
+            .partition_point(|(taken, _)| taken.start < span.start);
+        self.spans.insert(idx, (span, key));
+    }
+
+    /// Releases `span`, if it was taken by `key`.
+    pub fn remove(&mut self, span: &Span, key: K) {
+        let idx = self
+            .spans
+            .partition_point(|(taken, _)| taken.start < span.start);
+        if let Some((taken, owner)) = self.spans.get(idx)
+            && taken == span
+            && *owner == key
+        {
+            self.spans.remove(idx);
+        }
+    }
+}
+
+impl<K> Default for Timeline<K> {
+    fn default() -> Self {
+        Self { spans: Vec::new() }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::test::d;
+
+    fn span(start: u32, end: u32) -> Span {
+        Span::new(d(2025, 10, 23, start, 0, 0), d(2025, 10, 23, end, 0, 0))
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(span(10, 12).overlaps(&span(11, 13)));
+        assert!(span(10, 12).overlaps(&span(9, 11)));
+        assert!(span(10, 12).overlaps(&span(10, 12)));
+        assert!(span(10, 14).overlaps(&span(11, 12)));
+
+        assert!(!span(10, 12).overlaps(&span(12, 13)));
+        assert!(!span(10, 12).overlaps(&span(8, 10)));
+    }
+
+    #[test]
+    fn test_is_free() {
+        let mut sut = Timeline::default();
+        sut.insert(span(10, 12), 0);
+        sut.insert(span(14, 15), 1);
+
+        assert!(sut.is_free(&span(8, 10)));
+        assert!(sut.is_free(&span(12, 14)));
+        assert!(sut.is_free(&span(15, 20)));
+
+        assert!(!sut.is_free(&span(11, 12)));
+        assert!(!sut.is_free(&span(13, 15)));
+        assert!(!sut.is_free(&span(9, 16)));
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let mut sut = Timeline::default();
+        sut.insert(span(10, 12), 0);
+        sut.insert(span(12, 13), 1);
+        sut.insert(span(14, 15), 2);
+
+        let owners: Vec<_> = sut.overlapping(&span(11, 15)).map(|(_, k)| *k).collect();
+        assert_eq!(vec![0, 1, 2], owners);
+
+        let owners: Vec<_> = sut.overlapping(&span(12, 14)).map(|(_, k)| *k).collect();
+        assert_eq!(vec![1], owners);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut sut = Timeline::default();
+        sut.insert(span(10, 12), 0);
+
+        sut.remove(&span(10, 12), 1);
+        assert!(!sut.is_free(&span(10, 12)));
+
+        sut.remove(&span(10, 12), 0);
+        assert!(sut.is_free(&span(10, 12)));
+    }
+}
@@ -0,0 +1,444 @@
+use chrono::DateTime;
+use chrono::Local;
+use chrono::TimeDelta;
+
+use crate::planner::timeline::Span;
+use crate::sequencer::Sequencer;
+use crate::types::Blueprint;
+use crate::types::Duration;
+use crate::types::Priority;
+use crate::types::experimental::plan::AtRisk;
+use crate::types::experimental::plan::Overdue;
+use crate::types::experimental::plan_entry::PlanEntry;
+
+/// The planning state of a single blueprint.
+pub struct Track {
+    blueprint: Blueprint,
+    sequencer: Sequencer,
+    placements: Vec<Placement>,
+    overdue: usize,
+    frozen: bool,
+    previous: Vec<DateTime<Local>>,
+    pins: Vec<DateTime<Local>>,
+    pinned: usize,
+    done: TimeDelta,
+    before: TimeDelta,
+    after: TimeDelta,
+    completed_at: Option<DateTime<Local>>,
+}
+
+/// An entry placed by a track, along with the state of the track right
+/// before it was placed, so that it can be rolled back.
+struct Placement {
+    entry: PlanEntry,
+    before: Sequencer,
+    done: TimeDelta,
+    pinned: bool,
+    completes: bool,
+}
+
+impl Track {
+    pub fn new(blueprint: Blueprint, sequencer: Sequencer) -> Self {
+        let (before, after) = buffers(&blueprint, None);
+        Self {
+            blueprint,
+            sequencer,
+            placements: Vec::new(),
+            overdue: 0,
+            frozen: false,
+            previous: Vec::new(),
+            pins: Vec::new(),
+            pinned: 0,
+            done: TimeDelta::zero(),
+            before,
+            after,
+            completed_at: None,
+        }
+    }
+
+    /// Sets when the last occurrence was completed, according to the
+    /// journal.
+    pub const fn with_completed_at(mut self, completed_at: Option<DateTime<Local>>) -> Self {
+        self.completed_at = completed_at;
+        self
+    }
+
+    /// Keeps at least `min_gap` free after every entry.
+    pub fn with_min_gap(mut self, min_gap: Option<Duration>) -> Self {
+        (self.before, self.after) = buffers(&self.blueprint, min_gap);
+        self
+    }
+
+    /// Sets the work already done on the pending occurrence.
+    pub const fn with_progress(mut self, done: TimeDelta) -> Self {
+        self.done = done;
+        self
+    }
+
+    /// Sets the times at which the upcoming occurrences are pinned, which
+    /// are placed as the sweep reaches them, see
+    /// [commit_pins](Track::commit_pins).
+    pub fn with_pins(mut self, mut pins: Vec<DateTime<Local>>) -> Self {
+        pins.sort();
+        self.pins = pins;
+        self
+    }
+
+    /// Sets the times at which the upcoming occurrences were planned
+    /// previously, which are kept whenever they are still valid.
+    pub fn with_previous(mut self, mut previous: Vec<DateTime<Local>>) -> Self {
+        previous.sort();
+        self.previous = previous;
+        self
+    }
+
+    /// Places `entries` as they are, and freezes the track, so that it does
+    /// not place any further occurrence until it is rolled back.
+    pub fn restore<'e>(&mut self, entries: impl IntoIterator<Item = &'e PlanEntry>) {
+        for entry in entries {
+            let pinned = self.pins.contains(&entry.planned_for());
+            self.push(span_of(entry), pinned);
+            if pinned {
+                self.pinned += 1;
+            }
+        }
+        self.frozen = true;
+    }
+
+    /// Returns true if every pin is among `entries`.
+    pub fn restores_pins<'e>(&self, entries: impl IntoIterator<Item = &'e PlanEntry>) -> bool {
+        let restored: Vec<_> = entries.into_iter().map(PlanEntry::planned_for).collect();
+        self.pins.iter().all(|pin| restored.contains(pin))
+    }
+
+    /// Returns the spans of the pins that are not placed yet, in order.
+    pub fn pending_pins(&self) -> impl Iterator<Item = Span> {
+        self.pins[self.pinned..]
+            .iter()
+            .map(|ts| Span::new(*ts, *ts + self.blueprint.estimated_duration().timedelta()))
+    }
+
+    /// Places the pending pins up to `ts`, now that the sweep reached it.
+    ///
+    /// Each pin stands for the occurrence that is pending by then, so the
+    /// sequence goes on from the pinned time.
+    pub fn commit_pins(&mut self, ts: DateTime<Local>) {
+        while let Some(pin) = self.pins.get(self.pinned).copied().filter(|pin| *pin <= ts) {
+            let duration = self.blueprint.estimated_duration().timedelta();
+            self.push(Span::new(pin, pin + duration), true);
+            self.pinned += 1;
+        }
+    }
+
+    /// Returns true if an entry at `ts` would take the occurrence that the
+    /// next pending pin stands for, that is, if the sequence would not accept
+    /// another occurrence before the pin after it.
+    pub fn yields_to_pin(&self, ts: DateTime<Local>) -> bool {
+        let Some(pin) = self.pins.get(self.pinned) else {
+            return false;
+        };
+        let mut after = self.sequencer.clone();
+        after.pin(ts);
+        after.next_candidate(ts).is_none_or(|next| *pin < next)
+    }
+
+    pub const fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Catches up with the occurrences that are overdue at `now`, following
+    /// the policy of the blueprint.
+    pub fn catch_up(&mut self, now: DateTime<Local>) {
+        self.overdue = self.sequencer.catch_up(now, self.blueprint.catch_up());
+    }
+
+    /// Catches up with the occurrences that came due during the blackout
+    /// `span`, following the policy of the blueprint, once it is over.
+    ///
+    /// Occurrences that were overdue already, or are being caught up with,
+    /// are left alone.
+    pub fn resume(&mut self, span: Span) {
+        if !self.sequencer.is_catching_up() && self.sequencer.overdue(span.start) == 0 {
+            self.sequencer.catch_up(span.end, self.blueprint.catch_up());
+        }
+    }
+
+    pub fn blueprint(&self) -> &Blueprint {
+        &self.blueprint
+    }
+
+    pub fn sequencer(&self) -> &Sequencer {
+        &self.sequencer
+    }
+
+    /// Returns the priority of the next occurrence, which is escalated when
+    /// it has been postponed repeatedly.
+    pub fn priority(&self) -> Priority {
+        self.blueprint
+            .priority()
+            .escalated(self.sequencer.escalation())
+    }
+
+    /// Returns the priority of the entry placed at `ts`.
+    pub fn priority_at(&self, ts: DateTime<Local>) -> Priority {
+        self.placements
+            .iter()
+            .find(|placement| placement.entry.planned_for() == ts)
+            .map_or_else(
+                || self.priority(),
+                |placement| {
+                    self.blueprint
+                        .priority()
+                        .escalated(placement.before.escalation())
+                },
+            )
+    }
+
+    /// Returns when the last occurrence is done, either as planned, or as
+    /// recorded in the journal.
+    pub fn completed_at(&self) -> Option<DateTime<Local>> {
+        self.placements
+            .iter()
+            .rev()
+            .find(|placement| placement.completes)
+            .map(|placement| span_of(&placement.entry).end)
+            .or(self.completed_at)
+    }
+
+    /// Returns the time at which the next occurrence was planned previously,
+    /// leaving pins aside.
+    pub fn previous(&self) -> Option<DateTime<Local>> {
+        let placed = self
+            .placements
+            .iter()
+            .filter(|placement| !placement.pinned)
+            .count();
+        self.previous
+            .iter()
+            .filter(|ts| !self.pins.contains(ts))
+            .nth(placed)
+            .copied()
+    }
+
+    /// Returns the time kept free before each entry.
+    pub const fn buffer_before(&self) -> TimeDelta {
+        self.before
+    }
+
+    /// Returns `span` along with the buffers kept free around it.
+    pub fn padded(&self, span: Span) -> Span {
+        Span::new(span.start - self.before, span.end + self.after)
+    }
+
+    /// Returns the work left to do on the pending occurrence.
+    fn remaining(&self) -> TimeDelta {
+        self.blueprint.estimated_duration().timedelta() - self.done
+    }
+
+    /// Returns the lengths the next entry may take, longest first.
+    ///
+    /// That is all the work left on the pending occurrence, unless the
+    /// blueprint is splittable. Then, it may also be any number of full hours
+    /// that is not below the minimum chunk, and that does not leave less
+    /// than a minimum chunk behind.
+    pub fn lengths(&self) -> Vec<TimeDelta> {
+        let remaining = self.remaining();
+        let mut lengths = vec![remaining];
+        if let Some(min_chunk) = self.blueprint.min_chunk().map(|d| d.timedelta()) {
+            let hours = (remaining.num_seconds() - 1) / 3600;
+            lengths.extend(
+                (1..=hours)
+                    .rev()
+                    .map(TimeDelta::hours)
+                    .filter(|length| *length >= min_chunk && remaining - *length >= min_chunk),
+            );
+        }
+        lengths
+    }
+
+    /// Returns true if the entry at `ts` is pinned, whether it is placed yet
+    /// or not.
+    pub fn is_pinned_at(&self, ts: DateTime<Local>) -> bool {
+        self.pins.contains(&ts)
+    }
+
+    /// Places the next entry at `span`, which completes the pending
+    /// occurrence once all of its work is planned.
+    pub fn place(&mut self, span: Span) {
+        self.push(span, false);
+    }
+
+    /// Places the next entry at `span`, which may be up to `by` earlier than
+    /// the spacing of the recurrence allows.
+    pub fn pull(&mut self, span: Span, by: TimeDelta) {
+        let before = self.sequencer.clone();
+        self.sequencer = before.pulled_forward(by);
+        self.push(span, false);
+        if let Some(placement) = self.placements.last_mut() {
+            placement.before = before;
+        }
+    }
+
+    fn push(&mut self, span: Span, pinned: bool) {
+        let before = self.sequencer.clone();
+        let done = self.done;
+        self.done += span.length();
+        let completes = pinned || self.done >= self.blueprint.estimated_duration().timedelta();
+        if completes {
+            if pinned {
+                self.sequencer.pin(span.start);
+            } else {
+                self.sequencer.commit(span.start);
+            }
+            self.done = TimeDelta::zero();
+        }
+        self.placements.push(Placement {
+            entry: PlanEntry::new(
+                self.blueprint.id().to_string(),
+                span.length().into(),
+                span.start,
+            )
+            .with_buffers(
+                (!self.before.is_zero()).then(|| self.before.into()),
+                (!self.after.is_zero()).then(|| self.after.into()),
+            ),
+            before,
+            done,
+            pinned,
+            completes,
+        });
+    }
+
+    /// Returns true if the track could place an occurrence within `span`,
+    /// earlier than the entry it has planned after it.
+    pub fn could_take(&self, span: &Span) -> bool {
+        let idx = self
+            .placements
+            .partition_point(|placement| placement.entry.planned_for() < span.start);
+        let (sequencer, next) = match self.placements.get(idx) {
+            Some(placement) => (&placement.before, Some(placement.entry.planned_for())),
+            None => (&self.sequencer, None),
+        };
+        sequencer
+            .next_candidate(span.start)
+            .is_some_and(|candidate| {
+                candidate < span.end && next.is_none_or(|next| candidate < next)
+            })
+    }
+
+    /// Removes every entry planned at or after `from`, restoring the
+    /// sequencer to the state it had before the earliest of them. Pinned
+    /// entries are never removed, but become pending again, so that they
+    /// are placed once more as the sweep reaches them. A frozen track that
+    /// loses entries is thawed, so that it plans the removed occurrences
+    /// again.
+    ///
+    /// Returns the removed entries that are not pinned.
+    pub fn rollback(&mut self, from: DateTime<Local>) -> Vec<PlanEntry> {
+        let idx = self
+            .placements
+            .partition_point(|placement| placement.entry.planned_for() < from);
+        let removed = self.placements.split_off(idx);
+        if let Some(first) = removed.first() {
+            self.sequencer = first.before.clone();
+            self.done = first.done;
+            self.frozen = false;
+        }
+        removed
+            .into_iter()
+            .filter_map(|placement| {
+                if placement.pinned {
+                    self.pinned -= 1;
+                    None
+                } else {
+                    Some(placement.entry)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the occurrences that end after their deadline, or that are
+    /// still pending when their deadline comes before `end`.
+    pub fn at_risk(&self, end: DateTime<Local>) -> Vec<AtRisk> {
+        let mut at_risk: Vec<_> = self
+            .placements
+            .iter()
+            .filter_map(|placement| {
+                let due = placement.before.due()?;
+                (span_of(&placement.entry).end > due).then(|| {
+                    AtRisk::new(
+                        self.blueprint.id().to_string(),
+                        due,
+                        Some(placement.entry.planned_for()),
+                    )
+                })
+            })
+            .collect();
+
+        if let Some(due) = self.sequencer.due()
+            && due < end
+            && !self.sequencer.is_exhausted()
+        {
+            at_risk.push(AtRisk::new(self.blueprint.id().to_string(), due, None));
+        }
+        at_risk
+    }
+
+    /// Returns the overdue occurrences, and how many catch ups got placed.
+    pub fn overdue(&self) -> Option<Overdue> {
+        (self.overdue > 0).then(|| {
+            Overdue::new(
+                self.blueprint.id().to_string(),
+                self.overdue,
+                self.blueprint.catch_up(),
+                self.placements
+                    .iter()
+                    .filter(|placement| placement.completes && placement.before.is_catching_up())
+                    .count(),
+            )
+        })
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &PlanEntry> {
+        self.placements.iter().map(|placement| &placement.entry)
+    }
+
+    pub fn into_entries(self) -> impl Iterator<Item = PlanEntry> {
+        self.placements.into_iter().map(|placement| placement.entry)
+    }
+}
+
+/// Returns the time kept free before and after the entries of `blueprint`.
+///
+/// The gap between two entries is at least `min_gap`, so it pads the end of
+/// entries whose own buffer after them is shorter.
+pub fn buffers(blueprint: &Blueprint, min_gap: Option<Duration>) -> (TimeDelta, TimeDelta) {
+    let of = |duration: Option<Duration>| duration.map_or_else(TimeDelta::zero, |d| d.timedelta());
+    (
+        of(blueprint.buffer_before()),
+        of(blueprint.buffer_after()).max(of(min_gap)),
+    )
+}
+
+/// Returns the span taken by `entry`, along with its buffers.
+pub fn padded_span_of(entry: &PlanEntry) -> Span {
+    let span = span_of(entry);
+    Span::new(
+        span.start
+            - entry
+                .buffer_before()
+                .map_or_else(TimeDelta::zero, |d| d.timedelta()),
+        span.end
+            + entry
+                .buffer_after()
+                .map_or_else(TimeDelta::zero, |d| d.timedelta()),
+    )
+}
+
+/// Returns the span taken by `entry`.
+pub fn span_of(entry: &PlanEntry) -> Span {
+    Span::new(
+        entry.planned_for(),
+        entry.planned_for() + entry.duration().timedelta(),
+    )
+}
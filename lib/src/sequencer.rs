@@ -1,7 +1,11 @@
 use chrono::DateTime;
 use chrono::Local;
+use chrono::TimeDelta;
 
 use crate::types::Blueprint;
+use crate::types::CatchUp;
+use crate::types::Deadline;
+use crate::types::Duration;
 use crate::types::Recurrence;
 use crate::types::Slot;
 use crate::types::experimental::journal::Action;
@@ -12,13 +16,28 @@ use crate::types::experimental::journal::Journal;
 /// Think of it as an iterator that:
 /// - Validates incoming timestamps against a slot (e.g., "every hour at minute
 ///   0")
-/// - Enforces spacing between accepted timestamps (from recurrence)
+/// - Enforces spacing between accepted timestamps (from recurrence), and the
+///   window they must fall in, when the recurrence has one
 /// - Tracks remaining count (stops after N occurrences)
+/// - Tracks the deadline of the next occurrence, if any
+/// - Catches up with overdue occurrences
+/// - Holds off postponed occurrences, escalating their priority
+/// - Rejects timestamps outside of the active period, or while paused
+#[derive(Debug, Clone)]
 pub struct Sequencer {
     slot: Slot,
     recurrence: Recurrence,
     remaining: Option<usize>,
     next_mininum_ts: Option<DateTime<Local>>,
+    next_maximum_ts: Option<DateTime<Local>>,
+    preferred_ts: Option<DateTime<Local>>,
+    catch_ups: usize,
+    postponed: usize,
+    deadline: Option<Deadline>,
+    due: Option<DateTime<Local>>,
+    active_from: Option<DateTime<Local>>,
+    active_until: Option<DateTime<Local>>,
+    paused: bool,
 }
 
 impl Sequencer {
@@ -32,25 +51,254 @@ impl Sequencer {
             recurrence,
             remaining: recurrence.remaining(),
             next_mininum_ts: last_committed_at.map(|ts| recurrence.spaced(ts)),
+            next_maximum_ts: last_committed_at.and_then(|ts| recurrence.limit(ts)),
+            preferred_ts: last_committed_at.and_then(|ts| recurrence.nominal(ts)),
+            catch_ups: 0,
+            postponed: 0,
+            deadline: None,
+            due: None,
+            active_from: None,
+            active_until: None,
+            paused: false,
         }
     }
 
     pub fn from(blueprint: &Blueprint, journal: &Journal) -> Self {
-        Self::new(
+        let completions =
+            journal.get_completions_for(blueprint.id(), blueprint.estimated_duration());
+        let last_committed_at = completions.last().map(|commit| commit.committed_at());
+        let mut sequencer = Self::new(
             blueprint.recurrence(),
             blueprint.preferred_slot(),
-            journal
-                .get_last_commit_for(blueprint.id())
-                .and_then(|commit| match commit.action() {
-                    Action::Completed => Some(commit.committed_at()),
-                    Action::Postponed => None,
-                }),
-        )
+            last_committed_at,
+        );
+        if let Some(ref mut r) = sequencer.remaining {
+            *r = r.saturating_sub(completions.len());
+        }
+        if let Some(expiry) = blueprint.expires_at() {
+            sequencer = sequencer.with_expiry(expiry, last_committed_at);
+        }
+        if let Some(deadline) = blueprint.deadline() {
+            sequencer = sequencer.with_deadline(deadline, last_committed_at);
+        }
+
+        sequencer = sequencer
+            .with_active_period(blueprint.active_from(), blueprint.active_until())
+            .with_paused(blueprint.is_paused());
+
+        let postponements = journal.get_postponements_for(blueprint.id());
+        if let Some(commit) = postponements.last()
+            && let Action::Postponed(postpone) = commit.action()
+        {
+            let times = postponements.len();
+            sequencer = sequencer
+                .with_postponement(postpone.not_before(commit.committed_at(), times), times);
+        }
+        sequencer
+    }
+
+    /// Holds off the next occurrence until `not_before`, after it has been
+    /// postponed `times` in a row.
+    ///
+    /// A window that closes before `not_before` no longer applies, the
+    /// occurrence was explicitly moved out of it.
+    pub fn with_postponement(mut self, not_before: DateTime<Local>, times: usize) -> Self {
+        if self.next_mininum_ts.is_none_or(|ts| ts < not_before) {
+            self.next_mininum_ts = Some(not_before);
+        }
+        if self.next_maximum_ts.is_some_and(|ts| ts < not_before) {
+            self.next_maximum_ts = None;
+        }
+        if self.preferred_ts.is_some_and(|ts| ts < not_before) {
+            self.preferred_ts = Some(not_before);
+        }
+        self.postponed = times;
+        self
+    }
+
+    /// Bounds the occurrences in the sequence to the period `[from, until)`.
+    /// Missing bounds leave the period open on that side.
+    pub const fn with_active_period(
+        mut self,
+        from: Option<DateTime<Local>>,
+        until: Option<DateTime<Local>>,
+    ) -> Self {
+        self.active_from = from;
+        self.active_until = until;
+        self
+    }
+
+    /// Rejects every timestamp while `paused`.
+    pub const fn with_paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Returns true if `ts` falls within the active period, and the sequence
+    /// is not paused.
+    pub fn is_active_at(&self, ts: DateTime<Local>) -> bool {
+        !self.paused
+            && self.active_from.is_none_or(|from| from <= ts)
+            && self.active_until.is_none_or(|until| ts < until)
+    }
+
+    /// Returns how many levels the priority of the next occurrence is raised
+    /// by. Every consecutive postponement after the first raises it by one.
+    pub const fn escalation(&self) -> usize {
+        self.postponed.saturating_sub(1)
+    }
+
+    /// Anchors the window of a [Recurrence::Lead] to `expiry`, instead of
+    /// counting from `last_committed_at`.
+    ///
+    /// The expiry is ignored once `last_committed_at` falls within its
+    /// window, as the occurrence that renews it already took place.
+    pub fn with_expiry(
+        mut self,
+        expiry: DateTime<Local>,
+        last_committed_at: Option<DateTime<Local>>,
+    ) -> Self {
+        if let Recurrence::Lead {
+            earliest, latest, ..
+        } = self.recurrence
+        {
+            let opens_at = expiry - earliest.timedelta();
+            if last_committed_at.is_none_or(|ts| ts < opens_at) {
+                self.next_mininum_ts = Some(opens_at);
+                self.next_maximum_ts = Some(expiry - latest.timedelta());
+            }
+        }
+        self
+    }
+
+    /// Bounds the occurrences in the sequence by `deadline`, counting from
+    /// `last_committed_at`.
+    pub fn with_deadline(
+        mut self,
+        deadline: Deadline,
+        last_committed_at: Option<DateTime<Local>>,
+    ) -> Self {
+        self.deadline = Some(deadline);
+        self.due = deadline.due(last_committed_at);
+        self
+    }
+
+    /// Returns the deadline of the next occurrence in the sequence, if any.
+    ///
+    /// The latest timestamp allowed by the recurrence also acts as a
+    /// deadline.
+    pub fn due(&self) -> Option<DateTime<Local>> {
+        match (self.due, self.next_maximum_ts) {
+            (Some(due), Some(max)) => Some(due.min(max)),
+            (due, max) => due.or(max),
+        }
+    }
+
+    /// Returns the timestamp the recurrence prefers for the next occurrence,
+    /// if any.
+    pub const fn preferred(&self) -> Option<DateTime<Local>> {
+        self.preferred_ts
+    }
+
+    /// Returns how many occurrences are overdue at `now`, counted from the
+    /// last commit.
+    ///
+    /// An occurrence is overdue once its last valid placement is behind
+    /// `now`: the latest timestamp its recurrence allows, or else the end of
+    /// the first window of the slot where it may take place, up to when the
+    /// next occurrence is due. Only the active period counts, and nothing is
+    /// overdue while paused.
+    pub fn overdue(&self, now: DateTime<Local>) -> usize {
+        let Some(spacing) = self.recurrence.spacing() else {
+            return 0;
+        };
+        if self.paused {
+            return 0;
+        }
+        let lost = match (self.next_maximum_ts, self.next_mininum_ts) {
+            (Some(max), _) => self.active_from.map_or(max, |from| max.max(from)),
+            (None, Some(min)) => {
+                let earliest = self.active_from.map_or(min, |from| min.max(from));
+                let start = self.slot.next_chrono(earliest);
+                let next = earliest + spacing.timedelta();
+                self.slot
+                    .window_end(start)
+                    .map_or(next, |end| end.min(next))
+            }
+            (None, None) => return 0,
+        };
+        let now = self.active_until.map_or(now, |until| now.min(until));
+        if now <= lost {
+            return 0;
+        }
+
+        let spacing = spacing.timedelta().num_seconds().max(1);
+        let missed = 1 + ((now - lost).num_seconds() / spacing) as usize;
+        self.remaining.map_or(missed, |r| missed.min(r))
+    }
+
+    /// Applies the `policy` to the occurrences that are overdue at `now`.
+    ///
+    /// Returns how many occurrences were overdue.
+    pub fn catch_up(&mut self, now: DateTime<Local>, policy: CatchUp) -> usize {
+        let missed = self.overdue(now);
+        if missed == 0 {
+            return 0;
+        }
+
+        match policy {
+            CatchUp::Skip => {
+                let shift = self
+                    .recurrence
+                    .spacing()
+                    .map(|spacing| spacing.timedelta() * missed as i32)
+                    .unwrap_or_default();
+                self.next_mininum_ts = self.next_mininum_ts.map(|ts| ts + shift);
+                self.next_maximum_ts = self.next_maximum_ts.map(|ts| ts + shift);
+                self.preferred_ts = self.preferred_ts.map(|ts| ts + shift);
+                self.due = self.due.map(|ts| ts + shift);
+                if let Some(ref mut r) = self.remaining {
+                    *r = r.saturating_sub(missed);
+                }
+            }
+            CatchUp::Once | CatchUp::All => {
+                self.catch_ups = if policy == CatchUp::Once { 1 } else { missed };
+                self.next_maximum_ts = None;
+                self.preferred_ts = None;
+            }
+        }
+        missed
+    }
+
+    /// Returns true if the next occurrence catches up with an overdue one.
+    pub const fn is_catching_up(&self) -> bool {
+        self.catch_ups > 0
+    }
+
+    /// Returns true if there are no more occurrences in the sequence.
+    pub const fn is_exhausted(&self) -> bool {
+        matches!(self.remaining, Some(0))
+    }
+
+    /// Returns the sequence as if the next occurrence could take place `by`
+    /// earlier than its spacing allows.
+    pub fn pulled_forward(&self, by: TimeDelta) -> Self {
+        let mut sequencer = self.clone();
+        sequencer.next_mininum_ts = self.next_mininum_ts.map(|ts| ts - by);
+        sequencer
+    }
+
+    /// Returns true if the next occurrence is pending at `ts`, that is, its
+    /// spacing elapsed by then, and it is active.
+    pub fn is_pending_at(&self, ts: DateTime<Local>) -> bool {
+        !self.is_exhausted()
+            && self.is_active_at(ts)
+            && self.next_mininum_ts.is_none_or(|next| next <= ts)
     }
 
     /// Returns true if `ts` is a valid next timestamp in the sequence.
     pub fn accepts(&self, ts: DateTime<Local>) -> bool {
-        if let Some(0) = self.remaining {
+        if self.is_exhausted() || !self.is_active_at(ts) {
             return false;
         }
 
@@ -60,6 +308,12 @@ impl Sequencer {
             return false;
         }
 
+        if let Some(max) = self.next_maximum_ts
+            && ts > max
+        {
+            return false;
+        }
+
         if !self.slot.matches_chrono(ts) {
             return false;
         }
@@ -67,18 +321,69 @@ impl Sequencer {
         true
     }
 
+    /// Returns true if `ts` is a valid next timestamp in the sequence, and the
+    /// span of `duration` starting at `ts` fits entirely within the slot.
+    pub fn accepts_for(&self, ts: DateTime<Local>, duration: Duration) -> bool {
+        self.accepts(ts) && self.slot.fits_chrono(ts, ts + duration.timedelta())
+    }
+
+    /// Returns the earliest timestamp at or after `from` that the sequence
+    /// may accept, skipping over spacing and slot boundaries.
+    ///
+    /// Returns `None` once there are no more occurrences available, when
+    /// the window of the next occurrence or the active period is over, or
+    /// while paused.
+    pub fn next_candidate(&self, from: DateTime<Local>) -> Option<DateTime<Local>> {
+        if self.is_exhausted() || self.paused {
+            return None;
+        }
+
+        let ts = [self.next_mininum_ts, self.active_from]
+            .into_iter()
+            .flatten()
+            .fold(from, DateTime::max);
+        let ts = self.slot.next_chrono(ts);
+        match (self.next_maximum_ts, self.active_until) {
+            (Some(max), _) if ts > max => None,
+            (_, Some(until)) if ts >= until => None,
+            _ => Some(ts),
+        }
+    }
+
     /// Records `ts` as the next occurrence in the sequence.
     pub fn commit(&mut self, ts: DateTime<Local>) {
         debug_assert!(
             self.accepts(ts),
-            "always guard `next()` calls with `has_next()`"
+            "only commit timestamps that `accepts()` or `next_candidate()` yield"
         );
 
+        self.advance(ts);
+    }
+
+    /// Commits an occurrence that was pinned at `ts` by hand, which does not
+    /// need to be accepted.
+    pub fn pin(&mut self, ts: DateTime<Local>) {
+        self.advance(ts);
+    }
+
+    fn advance(&mut self, ts: DateTime<Local>) {
         if let Some(ref mut r) = self.remaining {
             *r = r.saturating_sub(1);
         }
+        self.postponed = 0;
+
+        if self.catch_ups > 1 {
+            // Pending catch ups are not spaced.
+            self.catch_ups -= 1;
+            self.next_mininum_ts = Some(ts);
+            return;
+        }
+        self.catch_ups = 0;
 
         self.next_mininum_ts = Some(self.recurrence.spaced(ts));
+        self.next_maximum_ts = self.recurrence.limit(ts);
+        self.preferred_ts = self.recurrence.nominal(ts);
+        self.due = self.deadline.and_then(|deadline| deadline.due_after(ts));
     }
 }
 
@@ -87,8 +392,10 @@ mod test {
 
     use super::*;
     use crate::test::d;
-    use crate::types::Duration;
     use crate::types::HourSlot;
+    use crate::types::Priority;
+    use crate::types::experimental::journal::Commit;
+    use crate::types::experimental::journal::Postpone;
 
     #[test]
     fn test_accepts() {
@@ -111,6 +418,56 @@ mod test {
         assert!(sut.accepts(ts));
     }
 
+    #[test]
+    fn test_accepts_for() {
+        let sut = Sequencer::new(
+            Recurrence::Once,
+            Slot::Hour(HourSlot::Range {
+                start: 10,
+                stop: 13,
+            }),
+            None,
+        );
+
+        let ts = d(2025, 10, 23, 11, 0, 0);
+        assert!(sut.accepts_for(ts, Duration::hours(3)));
+        assert!(!sut.accepts_for(ts, Duration::hours(4)));
+
+        let ts = d(2025, 10, 23, 13, 0, 0);
+        assert!(sut.accepts_for(ts, Duration::minutes(30)));
+        assert!(!sut.accepts_for(ts, Duration::hours(3)));
+    }
+
+    #[test]
+    fn test_next_candidate() {
+        let mut sut = Sequencer::new(
+            Recurrence::Times {
+                count: 2,
+                spacing: Duration::days(2),
+            },
+            Slot::Hour(HourSlot::Range { start: 3, stop: 5 }),
+            None,
+        );
+
+        let ts = d(2025, 10, 23, 4, 0, 0);
+        assert_eq!(Some(ts), sut.next_candidate(ts));
+        assert_eq!(
+            Some(d(2025, 10, 24, 3, 0, 0)),
+            sut.next_candidate(d(2025, 10, 23, 6, 0, 0))
+        );
+
+        // Skips over the spacing.
+        sut.commit(ts);
+        assert_eq!(
+            Some(d(2025, 10, 25, 4, 0, 0)),
+            sut.next_candidate(d(2025, 10, 23, 5, 0, 0))
+        );
+
+        // Exhausted.
+        sut.commit(d(2025, 10, 25, 4, 0, 0));
+        assert_eq!(None, sut.next_candidate(d(2025, 10, 26, 4, 0, 0)));
+    }
+
     #[test]
     fn test_commit() {
         let mut sut = Sequencer::new(
@@ -145,4 +502,258 @@ mod test {
         let ts = d(2025, 10, 27, 4, 0, 0);
         assert!(!sut.accepts(ts));
     }
+
+    #[test]
+    fn test_due() {
+        let last = d(2025, 10, 20, 4, 0, 0);
+        let mut sut = Sequencer::new(
+            Recurrence::Period {
+                spacing: Duration::days(2),
+            },
+            Slot::Hour(HourSlot::Range { start: 3, stop: 5 }),
+            Some(last),
+        )
+        .with_deadline(Deadline::Within(Duration::days(5)), Some(last));
+        assert_eq!(Some(d(2025, 10, 25, 4, 0, 0)), sut.due());
+
+        sut.commit(d(2025, 10, 23, 4, 0, 0));
+        assert_eq!(Some(d(2025, 10, 28, 4, 0, 0)), sut.due());
+
+        let mut sut = Sequencer::new(
+            Recurrence::Period {
+                spacing: Duration::days(2),
+            },
+            Slot::Hour(HourSlot::Range { start: 3, stop: 5 }),
+            Some(last),
+        )
+        .with_deadline(Deadline::At(d(2025, 10, 24, 0, 0, 0)), Some(last));
+        assert_eq!(Some(d(2025, 10, 24, 0, 0, 0)), sut.due());
+
+        sut.commit(d(2025, 10, 23, 4, 0, 0));
+        assert_eq!(None, sut.due());
+    }
+
+    #[test]
+    fn test_lead_window() {
+        let last = d(2025, 1, 1, 10, 0, 0);
+        let mut sut = Sequencer::new(
+            Recurrence::Lead {
+                period: Duration::days(100),
+                earliest: Duration::days(20),
+                latest: Duration::days(5),
+            },
+            Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            Some(last),
+        );
+
+        // Expires on 2025-04-11.
+        assert!(!sut.accepts(d(2025, 3, 21, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 3, 22, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 4, 6, 10, 0, 0)));
+        assert!(!sut.accepts(d(2025, 4, 7, 10, 0, 0)));
+        assert_eq!(Some(d(2025, 4, 6, 10, 0, 0)), sut.due());
+
+        assert_eq!(
+            Some(d(2025, 3, 22, 10, 0, 0)),
+            sut.next_candidate(d(2025, 2, 1, 0, 0, 0))
+        );
+        assert_eq!(None, sut.next_candidate(d(2025, 4, 6, 11, 0, 0)));
+
+        // Expires on 2025-07-06.
+        sut.commit(d(2025, 3, 28, 10, 0, 0));
+        assert!(!sut.accepts(d(2025, 6, 15, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 6, 16, 10, 0, 0)));
+        assert!(!sut.accepts(d(2025, 7, 2, 10, 0, 0)));
+    }
+
+    #[test]
+    fn test_with_expiry() {
+        let recurrence = Recurrence::Lead {
+            period: Duration::days(100),
+            earliest: Duration::days(20),
+            latest: Duration::days(5),
+        };
+        let slot = Slot::Hour(HourSlot::Fixed { hour: 10 });
+        let expiry = d(2025, 6, 30, 10, 0, 0);
+
+        let sut = Sequencer::new(recurrence, slot, None).with_expiry(expiry, None);
+        assert!(!sut.accepts(d(2025, 6, 9, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 6, 10, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 6, 25, 10, 0, 0)));
+        assert!(!sut.accepts(d(2025, 6, 26, 10, 0, 0)));
+
+        // Already renewed, the expiry is stale.
+        let renewed_at = d(2025, 6, 12, 10, 0, 0);
+        let sut = Sequencer::new(recurrence, slot, Some(renewed_at))
+            .with_expiry(expiry, Some(renewed_at));
+        assert!(!sut.accepts(d(2025, 6, 25, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 9, 1, 10, 0, 0)));
+    }
+
+    #[test]
+    fn test_about_window() {
+        let last = d(2025, 1, 1, 10, 0, 0);
+        let mut sut = Sequencer::new(
+            Recurrence::About {
+                spacing: Duration::days(100),
+                early: Duration::days(10),
+                late: Duration::days(20),
+            },
+            Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            Some(last),
+        );
+
+        // Nominally due on 2025-04-11.
+        assert_eq!(Some(d(2025, 4, 11, 10, 0, 0)), sut.preferred());
+        assert!(!sut.accepts(d(2025, 3, 31, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 4, 1, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 5, 1, 10, 0, 0)));
+        assert!(!sut.accepts(d(2025, 5, 2, 10, 0, 0)));
+
+        sut.commit(d(2025, 4, 5, 10, 0, 0));
+        assert_eq!(Some(d(2025, 7, 14, 10, 0, 0)), sut.preferred());
+    }
+
+    #[test]
+    fn test_overdue() {
+        let daily = Recurrence::Period {
+            spacing: Duration::days(1),
+        };
+        let slot = Slot::Hour(HourSlot::Range { start: 8, stop: 20 });
+        let last = d(2025, 10, 1, 10, 0, 0);
+
+        let sut = Sequencer::new(daily, slot, None);
+        assert_eq!(0, sut.overdue(d(2025, 10, 15, 9, 0, 0)));
+
+        let sut = Sequencer::new(daily, slot, Some(last));
+        assert_eq!(0, sut.overdue(d(2025, 10, 2, 10, 0, 0)));
+        // The slot is still open until 21:00.
+        assert_eq!(0, sut.overdue(d(2025, 10, 2, 11, 0, 0)));
+        assert_eq!(1, sut.overdue(d(2025, 10, 2, 21, 30, 0)));
+        assert_eq!(13, sut.overdue(d(2025, 10, 15, 9, 0, 0)));
+
+        let sut = Sequencer::new(
+            Recurrence::Times {
+                count: 3,
+                spacing: Duration::days(1),
+            },
+            slot,
+            Some(last),
+        );
+        assert_eq!(3, sut.overdue(d(2025, 10, 15, 9, 0, 0)));
+    }
+
+    #[test]
+    fn test_catch_up() {
+        let daily = Recurrence::Period {
+            spacing: Duration::days(1),
+        };
+        let slot = Slot::Hour(HourSlot::Range { start: 8, stop: 20 });
+        let last = d(2025, 10, 1, 10, 0, 0);
+        let now = d(2025, 10, 4, 9, 0, 0);
+
+        let mut sut = Sequencer::new(daily, slot, Some(last));
+        assert_eq!(2, sut.catch_up(now, CatchUp::Skip));
+        assert!(!sut.is_catching_up());
+        assert!(!sut.accepts(now));
+        assert!(sut.accepts(d(2025, 10, 4, 10, 0, 0)));
+
+        let mut sut = Sequencer::new(daily, slot, Some(last));
+        assert_eq!(2, sut.catch_up(now, CatchUp::Once));
+        assert!(sut.is_catching_up());
+        assert!(sut.accepts(now));
+        sut.commit(now);
+        assert!(!sut.is_catching_up());
+        assert!(!sut.accepts(d(2025, 10, 4, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 10, 5, 9, 0, 0)));
+
+        let mut sut = Sequencer::new(daily, slot, Some(last));
+        assert_eq!(2, sut.catch_up(now, CatchUp::All));
+        sut.commit(now);
+        assert!(sut.is_catching_up());
+        assert!(sut.accepts(d(2025, 10, 4, 10, 0, 0)));
+        sut.commit(d(2025, 10, 4, 10, 0, 0));
+        assert!(!sut.is_catching_up());
+        assert!(sut.accepts(d(2025, 10, 5, 10, 0, 0)));
+        assert!(!sut.accepts(d(2025, 10, 5, 9, 0, 0)));
+    }
+
+    #[test]
+    fn test_from_postponed() {
+        let blueprint = Blueprint::new(
+            "plants".to_string(),
+            "Water the plants".to_string(),
+            Duration::minutes(15),
+            Priority::Idle,
+            Recurrence::Period {
+                spacing: Duration::days(3),
+            },
+            Slot::Hour(HourSlot::Range { start: 8, stop: 20 }),
+        );
+        let journal = Journal::new(vec![
+            Commit::completed("plants".to_string(), d(2025, 10, 1, 10, 0, 0)),
+            Commit::postponed(
+                "plants".to_string(),
+                d(2025, 10, 4, 10, 0, 0),
+                Postpone::By(Duration::days(2)),
+            ),
+        ]);
+
+        let sut = Sequencer::from(&blueprint, &journal);
+        assert!(!sut.accepts(d(2025, 10, 6, 9, 0, 0)));
+        assert!(sut.accepts(d(2025, 10, 6, 10, 0, 0)));
+        assert_eq!(0, sut.escalation());
+
+        let journal = Journal::new(vec![
+            Commit::completed("plants".to_string(), d(2025, 10, 1, 10, 0, 0)),
+            Commit::postponed(
+                "plants".to_string(),
+                d(2025, 10, 4, 10, 0, 0),
+                Postpone::Backoff,
+            ),
+            Commit::postponed(
+                "plants".to_string(),
+                d(2025, 10, 5, 10, 0, 0),
+                Postpone::Backoff,
+            ),
+            Commit::postponed(
+                "plants".to_string(),
+                d(2025, 10, 7, 10, 0, 0),
+                Postpone::Backoff,
+            ),
+        ]);
+
+        let mut sut = Sequencer::from(&blueprint, &journal);
+        assert!(!sut.accepts(d(2025, 10, 11, 9, 0, 0)));
+        assert!(sut.accepts(d(2025, 10, 11, 10, 0, 0)));
+        assert_eq!(2, sut.escalation());
+
+        sut.commit(d(2025, 10, 11, 10, 0, 0));
+        assert_eq!(0, sut.escalation());
+    }
+
+    #[test]
+    fn test_active_period() {
+        let sut = Sequencer::new(
+            Recurrence::Period {
+                spacing: Duration::days(7),
+            },
+            Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            None,
+        )
+        .with_active_period(Some(d(2025, 10, 1, 0, 0, 0)), Some(d(2025, 12, 1, 0, 0, 0)));
+
+        assert!(!sut.accepts(d(2025, 9, 30, 10, 0, 0)));
+        assert!(sut.accepts(d(2025, 10, 1, 10, 0, 0)));
+        assert!(!sut.accepts(d(2025, 12, 1, 10, 0, 0)));
+        assert_eq!(
+            Some(d(2025, 10, 1, 10, 0, 0)),
+            sut.next_candidate(d(2025, 6, 1, 0, 0, 0))
+        );
+        assert_eq!(None, sut.next_candidate(d(2025, 11, 30, 11, 0, 0)));
+
+        let sut = sut.with_paused(true);
+        assert!(!sut.accepts(d(2025, 10, 1, 10, 0, 0)));
+        assert_eq!(None, sut.next_candidate(d(2025, 6, 1, 0, 0, 0)));
+    }
 }
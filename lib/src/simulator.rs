@@ -0,0 +1,424 @@
+use chrono::DateTime;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::TimeDelta;
+use chrono::Weekday;
+
+use crate::planner::Planner;
+use crate::types::Duration;
+use crate::types::experimental::book::Book;
+use crate::types::experimental::busy::Busy;
+use crate::types::experimental::journal::Commit;
+use crate::types::experimental::journal::Journal;
+use crate::types::experimental::journal::Postpone;
+use crate::types::experimental::plan::Plan;
+use crate::types::experimental::plan_entry::PlanEntry;
+
+/// How far ahead each plan of the simulation looks.
+const STEP: Duration = Duration::days(7);
+
+/// Simulates what happens to the blueprints of a [Book] over time, when
+/// planned entries are done, postponed, or skipped following a policy.
+///
+/// The simulation plans from `start`, and walks the entries of the plan in
+/// order. Each entry gets the outcome the policy decides for it, which is
+/// recorded in a copy of the journal as a [Commit] at the time the entry was
+/// planned for. As long as entries are completed as planned, the plan holds.
+/// Otherwise, everything is planned again right after the entry, with the
+/// journal as it is by then.
+///
+/// Without a policy, every entry is completed as planned.
+pub struct Simulator<'a> {
+    book: &'a Book,
+    journal: &'a Journal,
+    busy: &'a [Busy],
+    policy: Box<dyn Fn(&PlanEntry) -> Outcome + 'a>,
+}
+
+/// What happens to a planned entry when its time comes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    /// The entry is done as planned.
+    Completed,
+
+    /// The occurrence of the entry is postponed.
+    Postponed(Postpone),
+
+    /// The entry is not done, and its occurrence is left pending.
+    Skipped,
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(book: &'a Book, journal: &'a Journal) -> Self {
+        Self {
+            book,
+            journal,
+            busy: &[],
+            policy: Box::new(|_| Outcome::Completed),
+        }
+    }
+
+    /// Keeps the entries off the `busy` time, see
+    /// [with_busy](Planner::with_busy).
+    pub const fn with_busy(mut self, busy: &'a [Busy]) -> Self {
+        self.busy = busy;
+        self
+    }
+
+    /// Decides the outcome of each planned entry with `policy`.
+    pub fn with_policy(mut self, policy: impl Fn(&PlanEntry) -> Outcome + 'a) -> Self {
+        self.policy = Box::new(policy);
+        self
+    }
+
+    /// Simulates `[start, start + horizon)`.
+    pub fn simulate(&self, start: DateTime<Local>, horizon: Duration) -> Simulation {
+        let end = start + horizon.timedelta();
+        let mut journal = self.journal.clone();
+        let mut weeks = weeks(start, end);
+        let mut misses: Vec<Miss> = Vec::new();
+
+        let mut now = start;
+        while now < end {
+            let plan = self.plan(&journal, now, STEP);
+            let mut next = now + STEP.timedelta();
+            for entry in plan
+                .entries()
+                .iter()
+                .filter(|entry| now <= entry.planned_for() && entry.planned_for() < end)
+            {
+                let id = entry.blueprint_id().to_string();
+                let ts = entry.planned_for();
+                let week = &mut weeks[week_of(start, ts)];
+                // The planner places entries on full hours, so the next one
+                // is at least an hour later.
+                let entry_end = (ts + entry.duration().timedelta()).max(ts + TimeDelta::hours(1));
+                next = next.max(entry_end);
+
+                let outcome = (self.policy)(entry);
+                match outcome {
+                    Outcome::Completed => {
+                        let chunk = self.book.get(&id).is_some_and(|blueprint| {
+                            entry.duration().timedelta()
+                                < blueprint.estimated_duration().timedelta()
+                        });
+                        journal.record(if chunk {
+                            Commit::progressed(id.clone(), ts, entry.duration())
+                        } else {
+                            Commit::completed(id.clone(), ts)
+                        });
+                        week.done += 1;
+                        week.work += entry.duration().timedelta();
+                        if let Some(at_risk) = plan.at_risk().iter().find(|at_risk| {
+                            at_risk.blueprint_id() == id && at_risk.planned_for() == Some(ts)
+                        }) {
+                            push_miss(&mut misses, Miss::new(id, at_risk.deadline(), Some(ts)));
+                        }
+                    }
+                    Outcome::Postponed(postpone) => {
+                        journal.record(Commit::postponed(id, ts, postpone));
+                        week.postponed += 1;
+                    }
+                    Outcome::Skipped => week.skipped += 1,
+                }
+                if outcome != Outcome::Completed {
+                    next = entry_end;
+                    break;
+                }
+            }
+            now = next;
+        }
+
+        // Whatever is still due before the end did not get done in time.
+        let plan = self.plan(&journal, end, Duration::hours(0));
+        for at_risk in plan
+            .at_risk()
+            .iter()
+            .filter(|at_risk| at_risk.planned_for().is_none())
+        {
+            push_miss(
+                &mut misses,
+                Miss::new(at_risk.blueprint_id().to_string(), at_risk.deadline(), None),
+            );
+        }
+
+        Simulation {
+            weeks,
+            misses,
+            journal,
+        }
+    }
+
+    fn plan(&self, journal: &Journal, start: DateTime<Local>, horizon: Duration) -> Plan {
+        Planner::new(self.book, journal)
+            .with_busy(self.busy)
+            .plan(start, horizon)
+    }
+}
+
+/// Records `miss`, unless the same deadline of the same blueprint was
+/// already missed.
+fn push_miss(misses: &mut Vec<Miss>, miss: Miss) {
+    if !misses
+        .iter()
+        .any(|m| m.blueprint_id == miss.blueprint_id && m.deadline == miss.deadline)
+    {
+        misses.push(miss);
+    }
+}
+
+/// Returns the weeks that `[start, end)` touches, starting on Monday.
+fn weeks(start: DateTime<Local>, end: DateTime<Local>) -> Vec<WeekLoad> {
+    let last = if start < end {
+        end - TimeDelta::nanoseconds(1)
+    } else {
+        start
+    };
+    let first = monday_of(start);
+    (0..=week_of(start, last))
+        .map(|idx| WeekLoad::new(first + TimeDelta::weeks(idx as i64)))
+        .collect()
+}
+
+/// Returns the index of the week of `ts`, counting from the week of
+/// `start`.
+fn week_of(start: DateTime<Local>, ts: DateTime<Local>) -> usize {
+    ((monday_of(ts) - monday_of(start)).num_days() / 7) as usize
+}
+
+fn monday_of(ts: DateTime<Local>) -> NaiveDate {
+    ts.date_naive().week(Weekday::Mon).first_day()
+}
+
+/// The outcome of a [Simulator].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Simulation {
+    weeks: Vec<WeekLoad>,
+    misses: Vec<Miss>,
+    journal: Journal,
+}
+
+impl Simulation {
+    /// Returns the load of every week of the simulation, in order.
+    pub fn weeks(&self) -> &[WeekLoad] {
+        &self.weeks
+    }
+
+    /// Returns the deadlines that were missed, in the order they were
+    /// found.
+    pub fn misses(&self) -> &[Miss] {
+        &self.misses
+    }
+
+    /// Returns the journal, along with the commits of the simulation.
+    pub const fn journal(&self) -> &Journal {
+        &self.journal
+    }
+}
+
+/// Models what happened to the planned entries within a week.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct WeekLoad {
+    start: NaiveDate,
+    done: usize,
+    postponed: usize,
+    skipped: usize,
+    work: TimeDelta,
+}
+
+impl WeekLoad {
+    pub const fn new(start: NaiveDate) -> Self {
+        Self {
+            start,
+            done: 0,
+            postponed: 0,
+            skipped: 0,
+            work: TimeDelta::zero(),
+        }
+    }
+
+    /// Returns the Monday the week starts on.
+    pub const fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    /// Returns how many entries were completed.
+    pub const fn done(&self) -> usize {
+        self.done
+    }
+
+    /// Returns how many entries were postponed.
+    pub const fn postponed(&self) -> usize {
+        self.postponed
+    }
+
+    /// Returns how many entries were skipped.
+    pub const fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Returns how long the completed entries took altogether.
+    pub const fn work(&self) -> TimeDelta {
+        self.work
+    }
+}
+
+impl std::fmt::Display for WeekLoad {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} done {} ({}h{:02}) postponed {} skipped {}",
+            self.start,
+            self.done,
+            self.work.num_hours(),
+            self.work.num_minutes() % 60,
+            self.postponed,
+            self.skipped
+        )
+    }
+}
+
+/// Models a deadline that was missed, either by an occurrence completed
+/// after it, or by one that was not completed by the end of the simulation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Miss {
+    blueprint_id: String,
+    deadline: DateTime<Local>,
+    completed_at: Option<DateTime<Local>>,
+}
+
+impl Miss {
+    pub const fn new(
+        blueprint_id: String,
+        deadline: DateTime<Local>,
+        completed_at: Option<DateTime<Local>>,
+    ) -> Self {
+        Self {
+            blueprint_id,
+            deadline,
+            completed_at,
+        }
+    }
+
+    pub fn blueprint_id(&self) -> &str {
+        &self.blueprint_id
+    }
+
+    pub const fn deadline(&self) -> DateTime<Local> {
+        self.deadline
+    }
+
+    /// Returns when the occurrence was completed, or `None` if it was not.
+    pub const fn completed_at(&self) -> Option<DateTime<Local>> {
+        self.completed_at
+    }
+}
+
+impl std::fmt::Display for Miss {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} due {}",
+            self.blueprint_id,
+            self.deadline.to_rfc3339()
+        )?;
+        match self.completed_at {
+            Some(ts) => write!(f, " completed at {}", ts.to_rfc3339()),
+            None => f.write_str(" not completed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use chrono::Datelike;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test::blueprint;
+    use crate::test::d;
+    use crate::test::daily;
+    use crate::types::Deadline;
+    use crate::types::HourSlot;
+    use crate::types::Priority;
+    use crate::types::Recurrence;
+    use crate::types::Slot;
+
+    fn week(start: NaiveDate, done: usize, skipped: usize) -> WeekLoad {
+        let mut week = WeekLoad::new(start);
+        week.done = done;
+        week.skipped = skipped;
+        week.work = TimeDelta::hours(done as i64);
+        week
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_simulate_as_planned() {
+        let book = Book::new(vec![daily("gym", Priority::Norm, 9)]).unwrap();
+        let journal = Journal::new(vec![]);
+        // Wednesday.
+        let sut =
+            Simulator::new(&book, &journal).simulate(d(2025, 10, 22, 0, 0, 0), Duration::days(14));
+
+        assert_eq!(
+            &[
+                week(date(10, 20), 5, 0),
+                week(date(10, 27), 7, 0),
+                week(date(11, 3), 2, 0)
+            ],
+            sut.weeks()
+        );
+        assert!(sut.misses().is_empty());
+        assert_eq!(14, sut.journal().commits().len());
+        assert_eq!(
+            "2025-10-27 done 7 (7h00) postponed 0 skipped 0",
+            sut.weeks()[1].to_string()
+        );
+    }
+
+    #[test]
+    fn test_simulate_skips_weekend() {
+        let book = Book::new(vec![
+            daily("gym", Priority::Norm, 9),
+            blueprint(
+                "report",
+                Priority::Norm,
+                Recurrence::Once,
+                Slot::Hour(HourSlot::Fixed { hour: 10 }),
+            )
+            .with_active_from(d(2025, 11, 1, 0, 0, 0))
+            .with_deadline(Deadline::At(d(2025, 11, 1, 18, 0, 0))),
+        ])
+        .unwrap();
+        let journal = Journal::new(vec![]);
+        let weekend = d(2025, 11, 1, 0, 0, 0);
+        let sut = Simulator::new(&book, &journal)
+            .with_policy(|entry| {
+                let ts = entry.planned_for();
+                if ts >= weekend && ts.weekday().number_from_monday() > 5 {
+                    Outcome::Skipped
+                } else {
+                    Outcome::Completed
+                }
+            })
+            .simulate(d(2025, 10, 27, 0, 0, 0), Duration::days(8));
+
+        assert_eq!(
+            &[week(date(10, 27), 5, 4), week(date(11, 3), 2, 0)],
+            sut.weeks()
+        );
+        assert_eq!(
+            &[Miss::new(
+                "report".to_string(),
+                d(2025, 11, 1, 18, 0, 0),
+                Some(d(2025, 11, 3, 10, 0, 0))
+            )],
+            sut.misses()
+        );
+    }
+}
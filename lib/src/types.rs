@@ -1,14 +1,28 @@
 mod blueprint;
+mod catch_up;
 mod days;
+mod deadline;
+mod dependency;
+mod diagnostic;
 mod duration;
 pub mod experimental;
+mod parse_error;
 mod priority;
 mod recurrence;
 mod slots;
 mod timeunit;
 
 pub use blueprint::Blueprint;
+pub use catch_up::CatchUp;
+pub use days::DayOfWeek;
+pub use deadline::Deadline;
+pub use dependency::Dependency;
+pub use diagnostic::Diagnostic;
+pub use diagnostic::Issue;
+pub use diagnostic::Severity;
+pub use diagnostic::Subject;
 pub use duration::Duration;
+pub use parse_error::ParseError;
 pub use priority::Priority;
 pub use recurrence::Recurrence;
 pub use slots::HourSlot;
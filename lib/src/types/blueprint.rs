@@ -1,7 +1,18 @@
+use chrono::DateTime;
+use chrono::Local;
+use chrono::TimeDelta;
+
+use crate::types::CatchUp;
+use crate::types::Deadline;
+use crate::types::Dependency;
+use crate::types::Diagnostic;
 use crate::types::Duration;
+use crate::types::HourSlot;
+use crate::types::Issue;
 use crate::types::Priority;
 use crate::types::Recurrence;
 use crate::types::Slot;
+use crate::types::Subject;
 
 /// A template for creating recurring tasks or events.
 ///
@@ -10,6 +21,22 @@ use crate::types::Slot;
 /// - When it's preferred to be scheduled (`preferred_slot`)
 /// - How often it repeats (`recurrence`)
 /// - Its urgency level (`priority`)
+/// - Optionally, by when each occurrence must be done (`deadline`)
+/// - Optionally, when the current occurrence expires (`expires_at`), which
+///   anchors recurrences with a lead time
+/// - How to catch up with overdue occurrences (`catch_up`)
+/// - Optionally, the time to keep free before and after each occurrence
+///   (`buffer_before` and `buffer_after`), e.g. to travel
+/// - Optionally, the blueprints that must be completed before it
+///   (`dependencies`)
+/// - Optionally, labels of the groups it is done along with (`groups`), e.g.
+///   all the chores at the hardware store
+/// - Optionally, the smallest chunk each occurrence may be split into
+///   (`min_chunk`), when it does not need to be done in one go
+/// - Optionally, the period it is active in (`active_from` and `active_until`),
+///   e.g. for seasonal chores, and whether it is `paused`
+/// - Whether it may still be planned during blackouts that allow it
+///   (`travel_safe`)
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Blueprint {
     id: String,
@@ -18,6 +45,18 @@ pub struct Blueprint {
     priority: Priority,
     recurrence: Recurrence,
     preferred_slot: Slot,
+    deadline: Option<Deadline>,
+    expires_at: Option<DateTime<Local>>,
+    catch_up: CatchUp,
+    min_chunk: Option<Duration>,
+    buffer_before: Option<Duration>,
+    buffer_after: Option<Duration>,
+    dependencies: Vec<Dependency>,
+    groups: Vec<String>,
+    active_from: Option<DateTime<Local>>,
+    active_until: Option<DateTime<Local>>,
+    paused: bool,
+    travel_safe: bool,
 }
 
 impl Blueprint {
@@ -36,9 +75,85 @@ impl Blueprint {
             priority,
             recurrence,
             preferred_slot,
+            deadline: None,
+            expires_at: None,
+            catch_up: CatchUp::Once,
+            min_chunk: None,
+            buffer_before: None,
+            buffer_after: None,
+            dependencies: Vec::new(),
+            groups: Vec::new(),
+            active_from: None,
+            active_until: None,
+            paused: false,
+            travel_safe: false,
         }
     }
 
+    pub const fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub const fn with_expiry(mut self, expires_at: DateTime<Local>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    pub const fn with_catch_up(mut self, catch_up: CatchUp) -> Self {
+        self.catch_up = catch_up;
+        self
+    }
+
+    /// Allows occurrences to be split into chunks of at least `min_chunk`.
+    pub const fn with_split(mut self, min_chunk: Duration) -> Self {
+        self.min_chunk = Some(min_chunk);
+        self
+    }
+
+    /// Keeps `before` and `after` free around each occurrence.
+    pub const fn with_buffers(mut self, before: Duration, after: Duration) -> Self {
+        self.buffer_before = Some(before);
+        self.buffer_after = Some(after);
+        self
+    }
+
+    /// Waits for `dependency` to be completed before each occurrence.
+    pub fn with_dependency(mut self, dependency: Dependency) -> Self {
+        self.dependencies.push(dependency);
+        self
+    }
+
+    /// Adds the blueprint to the group labelled `group`.
+    pub fn with_group(mut self, group: String) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Plans no occurrences before `active_from`.
+    pub const fn with_active_from(mut self, active_from: DateTime<Local>) -> Self {
+        self.active_from = Some(active_from);
+        self
+    }
+
+    /// Plans no occurrences at or after `active_until`.
+    pub const fn with_active_until(mut self, active_until: DateTime<Local>) -> Self {
+        self.active_until = Some(active_until);
+        self
+    }
+
+    /// Plans no occurrences at all while `paused`.
+    pub const fn with_paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Allows occurrences during blackouts that are travel-safe.
+    pub const fn with_travel_safe(mut self, travel_safe: bool) -> Self {
+        self.travel_safe = travel_safe;
+        self
+    }
+
     pub fn id(&self) -> &str {
         &self.id
     }
@@ -62,6 +177,177 @@ impl Blueprint {
     pub const fn preferred_slot(&self) -> Slot {
         self.preferred_slot
     }
+
+    pub const fn deadline(&self) -> Option<Deadline> {
+        self.deadline
+    }
+
+    pub const fn expires_at(&self) -> Option<DateTime<Local>> {
+        self.expires_at
+    }
+
+    pub const fn catch_up(&self) -> CatchUp {
+        self.catch_up
+    }
+
+    pub const fn buffer_before(&self) -> Option<Duration> {
+        self.buffer_before
+    }
+
+    pub const fn buffer_after(&self) -> Option<Duration> {
+        self.buffer_after
+    }
+
+    pub fn dependencies(&self) -> &[Dependency] {
+        &self.dependencies
+    }
+
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    /// Returns true if both blueprints share any group.
+    pub fn shares_group(&self, other: &Blueprint) -> bool {
+        self.groups.iter().any(|group| other.groups.contains(group))
+    }
+
+    pub const fn active_from(&self) -> Option<DateTime<Local>> {
+        self.active_from
+    }
+
+    pub const fn active_until(&self) -> Option<DateTime<Local>> {
+        self.active_until
+    }
+
+    pub const fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub const fn is_travel_safe(&self) -> bool {
+        self.travel_safe
+    }
+
+    /// Returns the smallest chunk an occurrence may be split into, or `None`
+    /// if it is not splittable.
+    pub const fn min_chunk(&self) -> Option<Duration> {
+        self.min_chunk
+    }
+
+    /// Returns the issues that keep the blueprint from being planned as
+    /// intended, e.g. a slot that is too short for its duration.
+    ///
+    /// The planner places occurrences on full hours, and the window of the
+    /// recurrence is checked as if each occurrence were done when planned.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut issues = Vec::new();
+        if let Slot::Hour(slot) = self.preferred_slot {
+            let hours = match slot {
+                HourSlot::Fixed { hour } => vec![hour],
+                HourSlot::Range { start, stop } => vec![start, stop],
+            };
+            issues.extend(
+                hours
+                    .into_iter()
+                    .filter(|hour| *hour > 23)
+                    .map(Issue::InvalidHour),
+            );
+            if let HourSlot::Range { start, stop } = slot
+                && start == stop
+            {
+                issues.push(Issue::DegenerateRange(start));
+            }
+        }
+        if let Recurrence::Times { count: 0, .. } = self.recurrence {
+            issues.push(Issue::NoOccurrences);
+        }
+
+        // A slot that never matches is already reported by its hours.
+        let hours = self.preferred_slot.hours_of_week();
+        if hours.contains(&true) {
+            let duration = match self.min_chunk {
+                Some(min_chunk) if min_chunk.timedelta() < self.estimated_duration.timedelta() => {
+                    min_chunk
+                }
+                _ => self.estimated_duration,
+            };
+            if let Some(window) = longest_run(&hours)
+                && TimeDelta::hours(window as i64) < duration.timedelta()
+            {
+                issues.push(Issue::TooLong {
+                    duration,
+                    window: Duration::hours(window as u64),
+                });
+            }
+            if let Some((earliest, latest)) = window_of(self.recurrence)
+                && !meets_again(&hours, earliest, latest)
+            {
+                issues.push(Issue::Unsatisfiable);
+            }
+        }
+
+        issues
+            .into_iter()
+            .map(|issue| Diagnostic::new(Subject::Blueprint(self.id.clone()), issue))
+            .collect()
+    }
+}
+
+/// Returns the length in hours of the longest run of matching `hours`,
+/// wrapping around the end of the week, or `None` if every hour matches.
+fn longest_run(hours: &[bool]) -> Option<usize> {
+    let start = hours.iter().position(|matches| !matches)?;
+    let mut longest = 0;
+    let mut run = 0;
+    for idx in 1..=hours.len() {
+        if hours[(start + idx) % hours.len()] {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    Some(longest)
+}
+
+/// Returns when the occurrence that follows another one may take place,
+/// relative to it, or `None` if the recurrence does not bound it.
+fn window_of(recurrence: Recurrence) -> Option<(TimeDelta, TimeDelta)> {
+    match recurrence {
+        Recurrence::Lead {
+            period,
+            earliest,
+            latest,
+        } => Some((
+            period.timedelta() - earliest.timedelta(),
+            period.timedelta() - latest.timedelta(),
+        )),
+        Recurrence::About {
+            spacing,
+            early,
+            late,
+        } => Some((
+            spacing.timedelta() - early.timedelta(),
+            spacing.timedelta() + late.timedelta(),
+        )),
+        _ => None,
+    }
+}
+
+/// Returns true if an occurrence at some matching hour of the week is
+/// followed by a matching full hour within `[earliest, latest]` of it.
+fn meets_again(hours: &[bool], earliest: TimeDelta, latest: TimeDelta) -> bool {
+    let first = -(-earliest.num_seconds()).div_euclid(3600);
+    let last = latest.num_seconds().div_euclid(3600);
+    let len = hours.len() as i64;
+    if last < first {
+        return false;
+    }
+    if last - first >= len {
+        return true;
+    }
+    (0..len)
+        .filter(|hour| hours[*hour as usize])
+        .any(|hour| (first..=last).any(|offset| hours[(hour + offset).rem_euclid(len) as usize]))
 }
 
 impl std::fmt::Display for Blueprint {
@@ -83,7 +369,8 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::types::HourSlot;
+    use crate::types::DayOfWeek;
+    use crate::types::Severity;
     use crate::types::TimeUnit;
     use crate::types::WeekSlot;
 
@@ -120,4 +407,117 @@ mod test {
         );
         assert_eq!("1 CRIT ^3mo 1h Mon-Fri", sut.to_string());
     }
+
+    #[test]
+    fn test_validate() {
+        let sut = get_example_blueprint();
+        assert_eq!(Vec::<Diagnostic>::new(), sut.validate());
+
+        let issues = |slot: Slot, recurrence: Recurrence, duration: Duration| {
+            Blueprint::new(
+                "1".to_string(),
+                "Clean VAC filters".to_string(),
+                duration,
+                Priority::Idle,
+                recurrence,
+                slot,
+            )
+            .validate()
+            .into_iter()
+            .map(|diagnostic| diagnostic.issue().clone())
+            .collect::<Vec<_>>()
+        };
+        let once = Recurrence::Once;
+
+        assert_eq!(
+            vec![Issue::InvalidHour(25)],
+            issues(
+                Slot::Hour(HourSlot::Fixed { hour: 25 }),
+                once,
+                Duration::hours(1)
+            )
+        );
+        assert_eq!(
+            vec![Issue::DegenerateRange(9)],
+            issues(
+                Slot::Hour(HourSlot::Range { start: 9, stop: 9 }),
+                once,
+                Duration::hours(3)
+            )
+        );
+        assert_eq!(
+            vec![Issue::NoOccurrences],
+            issues(
+                Slot::Hour(HourSlot::Fixed { hour: 9 }),
+                Recurrence::Times {
+                    count: 0,
+                    spacing: Duration::days(1)
+                },
+                Duration::hours(1)
+            )
+        );
+
+        // From 22:00 to 03:00 is 6 hours in a row.
+        let night = Slot::Hour(HourSlot::Range { start: 22, stop: 3 });
+        assert!(issues(night, once, Duration::hours(6)).is_empty());
+        assert_eq!(
+            vec![Issue::TooLong {
+                duration: Duration::hours(7),
+                window: Duration::hours(6)
+            }],
+            issues(night, once, Duration::hours(7))
+        );
+        assert!(issues(Slot::Week(WeekSlot::full()), once, Duration::days(8)).is_empty());
+        let split = Blueprint::new(
+            "1".to_string(),
+            "Clean VAC filters".to_string(),
+            Duration::hours(7),
+            Priority::Idle,
+            once,
+            night,
+        )
+        .with_split(Duration::hours(2));
+        assert!(split.validate().is_empty());
+
+        // Done on a Monday, the next one is due between Wednesday and Friday.
+        let about = Recurrence::About {
+            spacing: Duration::days(3),
+            early: Duration::days(1),
+            late: Duration::days(1),
+        };
+        let monday = Slot::Week(WeekSlot::Fixed {
+            day: DayOfWeek::Mon,
+        });
+        assert_eq!(
+            vec![Issue::Unsatisfiable],
+            issues(monday, about, Duration::hours(1))
+        );
+        assert!(issues(Slot::Week(WeekSlot::workdays()), about, Duration::hours(1)).is_empty());
+        let lead = Recurrence::Lead {
+            period: Duration::days(30),
+            earliest: Duration::days(3),
+            latest: Duration::days(6),
+        };
+        assert_eq!(
+            vec![Issue::Unsatisfiable],
+            issues(Slot::Week(WeekSlot::full()), lead, Duration::hours(1))
+        );
+
+        let sut = Blueprint::new(
+            "1".to_string(),
+            "Clean VAC filters".to_string(),
+            Duration::hours(1),
+            Priority::Idle,
+            once,
+            Slot::Hour(HourSlot::Range { start: 9, stop: 30 }),
+        );
+        assert_eq!(
+            vec![Diagnostic::new(
+                Subject::Blueprint("1".to_string()),
+                Issue::InvalidHour(30)
+            )],
+            sut.validate()
+        );
+        assert_eq!(Severity::Error, sut.validate()[0].severity());
+    }
 }
@@ -0,0 +1,46 @@
+use crate::types::ParseError;
+
+/// How to deal with occurrences that became overdue.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum CatchUp {
+    /// Drops the missed occurrences, and waits for the next regular one.
+    Skip,
+
+    /// Plans a single occurrence as soon as possible, and resumes the
+    /// recurrence from there.
+    #[default]
+    Once,
+
+    /// Plans every missed occurrence as soon as possible, and resumes the
+    /// recurrence from the last of them.
+    All,
+}
+
+impl CatchUp {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            CatchUp::Skip => "skip",
+            CatchUp::Once => "once",
+            CatchUp::All => "all",
+        }
+    }
+}
+
+impl std::str::FromStr for CatchUp {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(CatchUp::Skip),
+            "once" => Ok(CatchUp::Once),
+            "all" => Ok(CatchUp::All),
+            _ => Err(ParseError::new("catch up", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for CatchUp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -3,6 +3,8 @@ use std::ops::Sub;
 
 use chrono::Weekday;
 
+use crate::types::ParseError;
+
 /// Models the days of the week.
 ///
 /// The natural ordering follows the alphabetical order based on variant names.
@@ -31,6 +33,23 @@ impl DayOfWeek {
     }
 }
 
+impl std::str::FromStr for DayOfWeek {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Mon" => Ok(DayOfWeek::Mon),
+            "Tue" => Ok(DayOfWeek::Tue),
+            "Wed" => Ok(DayOfWeek::Wed),
+            "Thu" => Ok(DayOfWeek::Thu),
+            "Fri" => Ok(DayOfWeek::Fri),
+            "Sat" => Ok(DayOfWeek::Sat),
+            "Sun" => Ok(DayOfWeek::Sun),
+            _ => Err(ParseError::new("day of week", s)),
+        }
+    }
+}
+
 impl std::fmt::Display for DayOfWeek {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.as_str())
@@ -0,0 +1,108 @@
+use chrono::DateTime;
+use chrono::Local;
+
+use crate::types::Duration;
+use crate::types::ParseError;
+
+/// The latest time by which an occurrence must be done.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Deadline {
+    /// A fixed point in time.
+    ///
+    /// Applies to the next pending occurrence only, once it is done the
+    /// deadline no longer applies.
+    At(DateTime<Local>),
+
+    /// A fixed amount of time after the previous occurrence.
+    ///
+    /// Applies to every occurrence. Without previous occurrences, there is
+    /// nothing to count from, and the deadline does not apply.
+    Within(Duration),
+}
+
+impl Deadline {
+    /// Returns the deadline of the next pending occurrence, given the
+    /// previous occurrence took place at `last`.
+    pub fn due(self, last: Option<DateTime<Local>>) -> Option<DateTime<Local>> {
+        match self {
+            Deadline::At(ts) => Some(ts),
+            Deadline::Within(duration) => last.map(|ts| ts + duration.timedelta()),
+        }
+    }
+
+    /// Returns the deadline of the occurrence that follows one planned at
+    /// `ts`.
+    pub fn due_after(self, ts: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Deadline::At(_) => None,
+            Deadline::Within(duration) => Some(ts + duration.timedelta()),
+        }
+    }
+}
+
+impl std::str::FromStr for Deadline {
+    type Err = ParseError;
+
+    /// Parses "!" followed by either a duration, or an RFC 3339 timestamp.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new("deadline", s);
+        let deadline = s.strip_prefix('!').ok_or_else(error)?;
+        if let Ok(duration) = deadline.parse() {
+            return Ok(Deadline::Within(duration));
+        }
+        DateTime::parse_from_rfc3339(deadline)
+            .map(|ts| Deadline::At(ts.with_timezone(&Local)))
+            .map_err(|_| error())
+    }
+}
+
+impl std::fmt::Display for Deadline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Deadline::At(ts) => write!(f, "!{}", ts.to_rfc3339()),
+            Deadline::Within(duration) => write!(f, "!{}", duration),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use chrono::TimeDelta;
+
+    use super::*;
+    use crate::test::d;
+    use crate::types::TimeUnit;
+
+    #[test]
+    fn test_display() {
+        let sut = Deadline::Within(Duration::of(3, TimeUnit::Month));
+        assert_eq!("!3mo", sut.to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(Deadline::Within(Duration::days(3))), "!3d".parse());
+
+        let sut = Deadline::At(d(2025, 10, 23, 10, 0, 0));
+        assert_eq!(Ok(sut), sut.to_string().parse());
+
+        assert!("3d".parse::<Deadline>().is_err());
+        assert!("!tomorrow".parse::<Deadline>().is_err());
+    }
+
+    #[test]
+    fn test_due() {
+        let ts = d(2025, 10, 23, 14, 0, 0);
+
+        let sut = Deadline::At(ts);
+        assert_eq!(Some(ts), sut.due(None));
+        assert_eq!(Some(ts), sut.due(Some(ts - TimeDelta::days(2))));
+        assert_eq!(None, sut.due_after(ts - TimeDelta::days(2)));
+
+        let sut = Deadline::Within(Duration::days(2));
+        assert_eq!(None, sut.due(None));
+        assert_eq!(Some(ts + TimeDelta::days(2)), sut.due(Some(ts)));
+        assert_eq!(Some(ts + TimeDelta::days(2)), sut.due_after(ts));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::types::Duration;
+use crate::types::ParseError;
+
+/// A blueprint that must be completed before the occurrences of another one.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Dependency {
+    blueprint_id: String,
+    delay: Option<Duration>,
+}
+
+impl Dependency {
+    pub const fn new(blueprint_id: String) -> Self {
+        Self {
+            blueprint_id,
+            delay: None,
+        }
+    }
+
+    /// Waits at least `delay` after the prerequisite is completed.
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    pub fn blueprint_id(&self) -> &str {
+        &self.blueprint_id
+    }
+
+    pub const fn delay(&self) -> Option<Duration> {
+        self.delay
+    }
+}
+
+impl std::str::FromStr for Dependency {
+    type Err = ParseError;
+
+    /// Parses a dependency, as displayed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new("dependency", s);
+        let dependency = s.strip_prefix("after ").ok_or_else(error)?;
+        match dependency.split_once(" +") {
+            Some((id, delay)) => {
+                Ok(Dependency::new(id.to_string()).with_delay(delay.parse().map_err(|_| error())?))
+            }
+            None => Ok(Dependency::new(dependency.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for Dependency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "after {}", self.blueprint_id)?;
+        match self.delay {
+            Some(delay) => write!(f, " +{delay}"),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let sut = Dependency::new("kit".to_string());
+        assert_eq!("after kit", sut.to_string());
+
+        let sut = sut.with_delay(Duration::days(1));
+        assert_eq!("after kit +1d", sut.to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let sut = Dependency::new("kit".to_string());
+        assert_eq!(Ok(sut.clone()), "after kit".parse());
+
+        let sut = sut.with_delay(Duration::days(1));
+        assert_eq!(Ok(sut), "after kit +1d".parse());
+
+        assert!("kit".parse::<Dependency>().is_err());
+        assert!("after kit +soon".parse::<Dependency>().is_err());
+    }
+}
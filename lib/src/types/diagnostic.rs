@@ -0,0 +1,198 @@
+use crate::types::Duration;
+use crate::types::experimental::anti_affinity::Target;
+
+/// Models an issue found while validating a [Blueprint] or a [Book].
+///
+/// [Blueprint]: crate::types::Blueprint
+/// [Book]: crate::types::experimental::book::Book
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    subject: Subject,
+    issue: Issue,
+}
+
+impl Diagnostic {
+    pub const fn new(subject: Subject, issue: Issue) -> Self {
+        Self { subject, issue }
+    }
+
+    pub const fn subject(&self) -> &Subject {
+        &self.subject
+    }
+
+    pub const fn issue(&self) -> &Issue {
+        &self.issue
+    }
+
+    pub const fn severity(&self) -> Severity {
+        self.issue.severity()
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.severity(), self.subject, self.issue)
+    }
+}
+
+/// What a [Diagnostic] is about.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Subject {
+    /// The book as a whole.
+    Book,
+
+    /// The blueprint with the given id.
+    Blueprint(String),
+
+    /// The blackout with the given name.
+    Blackout(String),
+}
+
+impl std::fmt::Display for Subject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Subject::Book => f.write_str("book"),
+            Subject::Blueprint(id) => write!(f, "blueprint {id}"),
+            Subject::Blackout(name) => write!(f, "blackout {name}"),
+        }
+    }
+}
+
+/// How much an [Issue] gets in the way of planning.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Severity {
+    /// The subject takes effect, though likely not as intended.
+    Warning,
+
+    /// The subject never takes effect as it is.
+    Error,
+}
+
+impl Severity {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The issues that validation finds.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Issue {
+    /// The slot refers to an hour past 23, which never comes.
+    InvalidHour(u32),
+
+    /// The slot is a range that starts and stops at the same hour, which
+    /// covers the whole day rather than that hour alone.
+    DegenerateRange(u32),
+
+    /// The recurrence repeats zero times.
+    NoOccurrences,
+
+    /// Occurrences, or their smallest chunk when split, take `duration`,
+    /// while the slot is never free for longer than `window` in a row.
+    TooLong {
+        duration: Duration,
+        window: Duration,
+    },
+
+    /// Once an occurrence is done within the slot, the window of the
+    /// recurrence never meets the slot again.
+    Unsatisfiable,
+
+    /// Another blueprint of the book has the same id.
+    DuplicateId,
+
+    /// The blueprint depends on one that is not in the book.
+    UnknownDependency(String),
+
+    /// The blackout ends at or before it starts, so it blocks nothing.
+    EmptyBlackout,
+
+    /// An anti-affinity keeps apart a target that matches no blueprint.
+    UnknownTarget(Target),
+}
+
+impl Issue {
+    pub const fn severity(&self) -> Severity {
+        match self {
+            Issue::DegenerateRange(_) => Severity::Warning,
+            Issue::InvalidHour(_)
+            | Issue::NoOccurrences
+            | Issue::TooLong { .. }
+            | Issue::Unsatisfiable
+            | Issue::DuplicateId
+            | Issue::UnknownDependency(_)
+            | Issue::EmptyBlackout
+            | Issue::UnknownTarget(_) => Severity::Error,
+        }
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::InvalidHour(hour) => write!(f, "hour {hour} is out of range"),
+            Issue::DegenerateRange(hour) => {
+                write!(f, "range {hour:02}:00-{hour:02}:00 covers the whole day")
+            }
+            Issue::NoOccurrences => f.write_str("recurrence has no occurrences"),
+            Issue::TooLong { duration, window } => {
+                write!(f, "{duration} does not fit in a slot of at most {window}")
+            }
+            Issue::Unsatisfiable => f.write_str("recurrence never meets the slot again"),
+            Issue::DuplicateId => f.write_str("id is not unique"),
+            Issue::UnknownDependency(id) => write!(f, "depends on unknown blueprint {id}"),
+            Issue::EmptyBlackout => f.write_str("ends at or before it starts"),
+            Issue::UnknownTarget(target) => {
+                write!(f, "anti-affinity target {target} matches no blueprint")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let sut = Diagnostic::new(
+            Subject::Blueprint("gym".to_string()),
+            Issue::TooLong {
+                duration: Duration::hours(3),
+                window: Duration::hours(1),
+            },
+        );
+        assert_eq!(
+            "error: blueprint gym: 3h does not fit in a slot of at most 1h",
+            sut.to_string()
+        );
+
+        let sut = Diagnostic::new(
+            Subject::Blueprint("gym".to_string()),
+            Issue::DegenerateRange(9),
+        );
+        assert_eq!(
+            "warning: blueprint gym: range 09:00-09:00 covers the whole day",
+            sut.to_string()
+        );
+
+        let sut = Diagnostic::new(
+            Subject::Book,
+            Issue::UnknownTarget(Target::Group("outdoor".to_string())),
+        );
+        assert_eq!(
+            "error: book: anti-affinity target @outdoor matches no blueprint",
+            sut.to_string()
+        );
+    }
+}
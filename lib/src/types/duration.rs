@@ -1,5 +1,6 @@
 use chrono::TimeDelta;
 
+use crate::types::ParseError;
 use crate::types::TimeUnit;
 
 /// Represents a fixed amount of time in a given unit (e.g. hours, minutes).
@@ -46,6 +47,35 @@ impl std::fmt::Display for Duration {
     }
 }
 
+impl std::str::FromStr for Duration {
+    type Err = ParseError;
+
+    /// Parses the amount followed by the unit, e.g. "3h" or "90min".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new("duration", s);
+        let at = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(error)?;
+        let (amount, unit) = s.split_at(at);
+        let amount = amount.parse().map_err(|_| error())?;
+        let unit = unit.parse().map_err(|_| error())?;
+        Ok(Duration::of(amount, unit))
+    }
+}
+
+impl From<TimeDelta> for Duration {
+    /// Converts `delta` into the largest unit that keeps it exact, ignoring
+    /// anything below a second.
+    fn from(delta: TimeDelta) -> Self {
+        let seconds = delta.num_seconds().max(0) as u64;
+        if seconds.is_multiple_of(3600) {
+            Duration::hours(seconds / 3600)
+        } else if seconds.is_multiple_of(60) {
+            Duration::minutes(seconds / 60)
+        } else {
+            Duration::of(seconds, TimeUnit::Second)
+        }
+    }
+}
+
 impl std::ops::Add for Duration {
     type Output = Duration;
 
@@ -80,6 +110,32 @@ mod test {
         assert_eq!(d.timedelta(), TimeDelta::days(365 * 123));
     }
 
+    #[test]
+    fn test_from_timedelta() {
+        assert_eq!(Duration::hours(3), Duration::from(TimeDelta::hours(3)));
+        assert_eq!(
+            Duration::minutes(90),
+            Duration::from(TimeDelta::minutes(90))
+        );
+        assert_eq!(
+            Duration::of(61, TimeUnit::Second),
+            Duration::from(TimeDelta::seconds(61))
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(Duration::hours(3)), "3h".parse());
+        assert_eq!(Ok(Duration::minutes(90)), "90min".parse());
+        assert_eq!(Ok(Duration::of(3, TimeUnit::Month)), "3mo".parse());
+        assert_eq!(
+            Err(ParseError::new("duration", "3m")),
+            "3m".parse::<Duration>()
+        );
+        assert!("h".parse::<Duration>().is_err());
+        assert!("12".parse::<Duration>().is_err());
+    }
+
     #[test]
     fn test_add() {
         let a = Duration::of(60, TimeUnit::Second);
@@ -1,4 +1,10 @@
+pub mod anti_affinity;
+pub mod blackout;
 pub mod book;
+pub mod busy;
+pub mod capacity;
+pub mod explanation;
 pub mod journal;
+pub mod pins;
 pub mod plan;
 pub mod plan_entry;
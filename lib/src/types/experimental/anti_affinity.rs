@@ -0,0 +1,249 @@
+use chrono::DateTime;
+use chrono::Local;
+
+use crate::types::Blueprint;
+use crate::types::Duration;
+use crate::types::ParseError;
+
+/// Keeps the occurrences of some blueprints apart from those of others.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AntiAffinity {
+    first: Target,
+    second: Target,
+    separation: Separation,
+    enforcement: Enforcement,
+}
+
+impl AntiAffinity {
+    /// Creates a hard constraint, which is never violated.
+    pub const fn new(first: Target, second: Target, separation: Separation) -> Self {
+        Self {
+            first,
+            second,
+            separation,
+            enforcement: Enforcement::Hard,
+        }
+    }
+
+    /// Turns the constraint into a soft one, which is violated rather than
+    /// delaying an occurrence by more than `penalty`.
+    pub const fn with_penalty(mut self, penalty: Duration) -> Self {
+        self.enforcement = Enforcement::Soft(penalty);
+        self
+    }
+
+    pub const fn first(&self) -> &Target {
+        &self.first
+    }
+
+    pub const fn second(&self) -> &Target {
+        &self.second
+    }
+
+    pub const fn separation(&self) -> Separation {
+        self.separation
+    }
+
+    pub const fn enforcement(&self) -> Enforcement {
+        self.enforcement
+    }
+
+    /// Returns true if the constraint keeps the occurrences of both
+    /// blueprints apart.
+    pub fn applies(&self, a: &Blueprint, b: &Blueprint) -> bool {
+        (self.first.matches(a) && self.second.matches(b))
+            || (self.first.matches(b) && self.second.matches(a))
+    }
+}
+
+impl std::str::FromStr for AntiAffinity {
+    type Err = ParseError;
+
+    /// Parses an anti-affinity, as displayed, where the separation may be
+    /// left out for a day, e.g. "budget <-> @cleaning" or "a <-> b 2d ~12h".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new("anti-affinity", s);
+        let words: Vec<_> = s.split_whitespace().collect();
+        let [first, "<->", second, rest @ ..] = words.as_slice() else {
+            return Err(error());
+        };
+        let anti_affinity = |separation: &str| -> Result<Self, ParseError> {
+            Ok(AntiAffinity::new(
+                first.parse().map_err(|_| error())?,
+                second.parse().map_err(|_| error())?,
+                separation.parse().map_err(|_| error())?,
+            ))
+        };
+        match rest {
+            [] => anti_affinity("day"),
+            [separation] => anti_affinity(separation),
+            [separation, penalty] => {
+                let penalty = penalty.strip_prefix('~').ok_or_else(error)?;
+                Ok(anti_affinity(separation)?.with_penalty(penalty.parse().map_err(|_| error())?))
+            }
+            _ => Err(error()),
+        }
+    }
+}
+
+impl std::fmt::Display for AntiAffinity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} <-> {} {}", self.first, self.second, self.separation)?;
+        match self.enforcement {
+            Enforcement::Hard => Ok(()),
+            Enforcement::Soft(penalty) => write!(f, " ~{penalty}"),
+        }
+    }
+}
+
+/// The blueprints an [AntiAffinity] applies to.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Target {
+    /// The blueprint with the given id.
+    Blueprint(String),
+
+    /// Every blueprint in the group with the given label.
+    Group(String),
+}
+
+impl Target {
+    pub fn matches(&self, blueprint: &Blueprint) -> bool {
+        match self {
+            Target::Blueprint(id) => blueprint.id() == id,
+            Target::Group(group) => blueprint.groups().contains(group),
+        }
+    }
+}
+
+impl std::str::FromStr for Target {
+    type Err = ParseError;
+
+    /// Parses a blueprint id, or a group label following "@".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('@') {
+            _ if s.is_empty() || s == "@" => Err(ParseError::new("target", s)),
+            Some(group) => Ok(Target::Group(group.to_string())),
+            None => Ok(Target::Blueprint(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Blueprint(id) => f.write_str(id),
+            Target::Group(group) => write!(f, "@{group}"),
+        }
+    }
+}
+
+/// How far apart occurrences must be.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Separation {
+    /// Not on the same day.
+    Day,
+
+    /// Starting at least the given amount of time apart.
+    AtLeast(Duration),
+}
+
+impl Separation {
+    /// Returns true if occurrences starting at `a` and `b` are far enough
+    /// apart.
+    pub fn separates(self, a: DateTime<Local>, b: DateTime<Local>) -> bool {
+        match self {
+            Separation::Day => a.date_naive() != b.date_naive(),
+            Separation::AtLeast(duration) => (a - b).abs() >= duration.timedelta(),
+        }
+    }
+}
+
+impl std::str::FromStr for Separation {
+    type Err = ParseError;
+
+    /// Parses "day", or a duration.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Separation::Day),
+            _ => s
+                .parse()
+                .map(Separation::AtLeast)
+                .map_err(|_| ParseError::new("separation", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for Separation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Separation::Day => f.write_str("day"),
+            Separation::AtLeast(duration) => write!(f, "{duration}"),
+        }
+    }
+}
+
+/// How strictly an [AntiAffinity] holds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Enforcement {
+    /// Never violated.
+    Hard,
+
+    /// Violated rather than delaying an occurrence by more than the given
+    /// penalty.
+    Soft(Duration),
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::test::d;
+
+    #[test]
+    fn test_separates() {
+        let sut = Separation::Day;
+        assert!(!sut.separates(d(2025, 10, 23, 9, 0, 0), d(2025, 10, 23, 20, 0, 0)));
+        assert!(sut.separates(d(2025, 10, 23, 23, 0, 0), d(2025, 10, 24, 0, 0, 0)));
+
+        let sut = Separation::AtLeast(Duration::days(2));
+        assert!(!sut.separates(d(2025, 10, 23, 9, 0, 0), d(2025, 10, 25, 8, 0, 0)));
+        assert!(sut.separates(d(2025, 10, 25, 9, 0, 0), d(2025, 10, 23, 9, 0, 0)));
+    }
+
+    #[test]
+    fn test_display() {
+        let sut = AntiAffinity::new(
+            Target::Blueprint("budget".to_string()),
+            Target::Group("cleaning".to_string()),
+            Separation::Day,
+        );
+        assert_eq!("budget <-> @cleaning day", sut.to_string());
+
+        let sut = sut.with_penalty(Duration::hours(12));
+        assert_eq!("budget <-> @cleaning day ~12h", sut.to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let sut = AntiAffinity::new(
+            Target::Blueprint("budget".to_string()),
+            Target::Group("cleaning".to_string()),
+            Separation::Day,
+        );
+        assert_eq!(Ok(sut.clone()), "budget <-> @cleaning".parse());
+        assert_eq!(Ok(sut.clone()), "budget <-> @cleaning day".parse());
+
+        let sut = AntiAffinity::new(
+            Target::Blueprint("a".to_string()),
+            Target::Blueprint("b".to_string()),
+            Separation::AtLeast(Duration::days(2)),
+        )
+        .with_penalty(Duration::hours(12));
+        assert_eq!(Ok(sut.clone()), sut.to_string().parse());
+
+        assert!("a b".parse::<AntiAffinity>().is_err());
+        assert!("a <-> @".parse::<AntiAffinity>().is_err());
+        assert!("a <-> b week".parse::<AntiAffinity>().is_err());
+        assert!("a <-> b day 12h".parse::<AntiAffinity>().is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::DateTime;
+use chrono::Local;
+
+use crate::types::Blueprint;
+
+/// A period of time `[start, end)` in which nothing gets planned, e.g. a
+/// vacation.
+///
+/// Blackouts may still allow the blueprints that are travel-safe, e.g. for
+/// a business trip.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Blackout {
+    name: String,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    travel_safe: bool,
+}
+
+impl Blackout {
+    /// Creates a blackout, which blocks nothing if `end` is at or before
+    /// `start`; [validate](crate::types::experimental::book::Book::validate)
+    /// reports those.
+    pub const fn new(name: String, start: DateTime<Local>, end: DateTime<Local>) -> Self {
+        Self {
+            name,
+            start,
+            end,
+            travel_safe: false,
+        }
+    }
+
+    /// Still allows the blueprints that are travel-safe.
+    pub const fn with_travel_safe(mut self) -> Self {
+        self.travel_safe = true;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub const fn start(&self) -> DateTime<Local> {
+        self.start
+    }
+
+    pub const fn end(&self) -> DateTime<Local> {
+        self.end
+    }
+
+    pub const fn is_travel_safe(&self) -> bool {
+        self.travel_safe
+    }
+
+    /// Returns true if the blackout keeps `blueprint` from being planned.
+    pub const fn blocks(&self, blueprint: &Blueprint) -> bool {
+        !(self.travel_safe && blueprint.is_travel_safe())
+    }
+}
+
+impl std::fmt::Display for Blackout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.name,
+            self.start.to_rfc3339(),
+            self.end.to_rfc3339()
+        )?;
+        if self.travel_safe {
+            f.write_str(" travel-safe")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::test::d;
+    use crate::types::Duration;
+    use crate::types::HourSlot;
+    use crate::types::Priority;
+    use crate::types::Recurrence;
+    use crate::types::Slot;
+
+    #[test]
+    fn test_blocks() {
+        let blueprint = Blueprint::new(
+            "mail".to_string(),
+            "Read the mail".to_string(),
+            Duration::hours(1),
+            Priority::Norm,
+            Recurrence::Period {
+                spacing: Duration::days(1),
+            },
+            Slot::Hour(HourSlot::Fixed { hour: 9 }),
+        );
+        let sut = Blackout::new(
+            "trip".to_string(),
+            d(2025, 8, 1, 0, 0, 0),
+            d(2025, 8, 15, 0, 0, 0),
+        );
+        assert!(sut.blocks(&blueprint));
+        assert!(sut.blocks(&blueprint.clone().with_travel_safe(true)));
+
+        let sut = sut.with_travel_safe();
+        assert!(sut.blocks(&blueprint));
+        assert!(!sut.blocks(&blueprint.with_travel_safe(true)));
+    }
+}
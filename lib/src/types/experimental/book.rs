@@ -1,43 +1,255 @@
 use std::cmp;
 
-use chrono::DateTime;
-use chrono::Local;
-use chrono::TimeDelta;
-
 use crate::sequencer::Sequencer;
 use crate::types::Blueprint;
+use crate::types::Diagnostic;
+use crate::types::Duration;
+use crate::types::Issue;
+use crate::types::Subject;
+use crate::types::experimental::anti_affinity::AntiAffinity;
+use crate::types::experimental::anti_affinity::Target;
+use crate::types::experimental::blackout::Blackout;
+use crate::types::experimental::capacity::Capacity;
 use crate::types::experimental::journal::Journal;
 
 /// Models a collection of blueprints.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Book {
     blueprints: Vec<Blueprint>,
+    capacity: Capacity,
+    min_gap: Option<Duration>,
+    pull_forward: Option<Duration>,
+    leveling: Option<Duration>,
+    anti_affinities: Vec<AntiAffinity>,
+    blackouts: Vec<Blackout>,
+}
+
+/// The reasons why a [Book] cannot be created.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum BookError {
+    /// Blueprints depend on each other in a cycle, which is given as the
+    /// blueprint ids along it, starting and ending with the same one.
+    Cycle(Vec<String>),
+}
+
+impl std::fmt::Display for BookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookError::Cycle(ids) => write!(f, "dependency cycle: {}", ids.join(" -> ")),
+        }
+    }
 }
 
+impl std::error::Error for BookError {}
+
 impl Book {
-    pub fn new(mut blueprints: Vec<Blueprint>) -> Self {
+    /// Creates a book out of `blueprints`, unless their dependencies form a
+    /// cycle.
+    pub fn new(mut blueprints: Vec<Blueprint>) -> Result<Self, BookError> {
         blueprints.sort_by_key(|b| cmp::Reverse(b.priority()));
-        Self { blueprints }
+        if let Some(cycle) = find_cycle(&blueprints) {
+            return Err(BookError::Cycle(cycle));
+        }
+        Ok(Self {
+            blueprints,
+            capacity: Capacity::unlimited(),
+            min_gap: None,
+            pull_forward: None,
+            leveling: None,
+            anti_affinities: Vec::new(),
+            blackouts: Vec::new(),
+        })
+    }
+
+    /// Limits how much work may be planned out of the book.
+    pub const fn with_capacity(mut self, capacity: Capacity) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Keeps at least `min_gap` free between any two planned entries.
+    pub const fn with_min_gap(mut self, min_gap: Duration) -> Self {
+        self.min_gap = Some(min_gap);
+        self
+    }
+
+    /// Lets occurrences be planned up to `pull_forward` early, so that they
+    /// are done along with others of the same group.
+    pub const fn with_pull_forward(mut self, pull_forward: Duration) -> Self {
+        self.pull_forward = Some(pull_forward);
+        self
+    }
+
+    /// Evens out the planned work across weeks, by planning occurrences up to
+    /// `tolerance` later than they may take place, in the week with the least
+    /// work.
+    pub const fn with_leveling(mut self, tolerance: Duration) -> Self {
+        self.leveling = Some(tolerance);
+        self
+    }
+
+    /// Keeps the occurrences of some blueprints apart from others.
+    pub fn with_anti_affinity(mut self, anti_affinity: AntiAffinity) -> Self {
+        self.anti_affinities.push(anti_affinity);
+        self
+    }
+
+    /// Keeps the occurrences out of `blackout`.
+    pub fn with_blackout(mut self, blackout: Blackout) -> Self {
+        self.blackouts.push(blackout);
+        self
     }
 
     pub fn blueprints(&self) -> &[Blueprint] {
         &self.blueprints
     }
 
+    /// Returns the blueprint with the given id.
+    pub fn get(&self, blueprint_id: &str) -> Option<&Blueprint> {
+        self.blueprints.iter().find(|bp| bp.id() == blueprint_id)
+    }
+
+    pub const fn capacity(&self) -> Capacity {
+        self.capacity
+    }
+
+    pub const fn min_gap(&self) -> Option<Duration> {
+        self.min_gap
+    }
+
+    pub const fn pull_forward(&self) -> Option<Duration> {
+        self.pull_forward
+    }
+
+    /// Returns the leveling tolerance, or `None` if work is not leveled.
+    pub const fn leveling(&self) -> Option<Duration> {
+        self.leveling
+    }
+
+    pub fn anti_affinities(&self) -> &[AntiAffinity] {
+        &self.anti_affinities
+    }
+
+    pub fn blackouts(&self) -> &[Blackout] {
+        &self.blackouts
+    }
+
+    /// Returns the issues of every blueprint, see
+    /// [validate](Blueprint::validate), along with those that span the book:
+    /// ids taken by more than one blueprint, reported once each, dependencies
+    /// on blueprints that are not in the book, blackouts that block nothing
+    /// and anti-affinity targets that match no blueprint.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics: Vec<_> = self
+            .blueprints
+            .iter()
+            .flat_map(Blueprint::validate)
+            .collect();
+        let mut seen: Vec<&str> = Vec::new();
+        let mut duplicates: Vec<&str> = Vec::new();
+        for bp in &self.blueprints {
+            if seen.contains(&bp.id()) {
+                if !duplicates.contains(&bp.id()) {
+                    duplicates.push(bp.id());
+                }
+            } else {
+                seen.push(bp.id());
+            }
+        }
+        diagnostics.extend(
+            duplicates
+                .into_iter()
+                .map(|id| Diagnostic::new(Subject::Blueprint(id.to_string()), Issue::DuplicateId)),
+        );
+        for bp in &self.blueprints {
+            diagnostics.extend(
+                bp.dependencies()
+                    .iter()
+                    .filter(|dependency| self.get(dependency.blueprint_id()).is_none())
+                    .map(|dependency| {
+                        Diagnostic::new(
+                            Subject::Blueprint(bp.id().to_string()),
+                            Issue::UnknownDependency(dependency.blueprint_id().to_string()),
+                        )
+                    }),
+            );
+        }
+        diagnostics.extend(
+            self.blackouts
+                .iter()
+                .filter(|blackout| blackout.end() <= blackout.start())
+                .map(|blackout| {
+                    Diagnostic::new(
+                        Subject::Blackout(blackout.name().to_string()),
+                        Issue::EmptyBlackout,
+                    )
+                }),
+        );
+        let mut unknown: Vec<&Target> = Vec::new();
+        for target in self
+            .anti_affinities
+            .iter()
+            .flat_map(|anti_affinity| [anti_affinity.first(), anti_affinity.second()])
+        {
+            if !self.blueprints.iter().any(|bp| target.matches(bp)) && !unknown.contains(&target) {
+                unknown.push(target);
+            }
+        }
+        diagnostics.extend(
+            unknown
+                .into_iter()
+                .map(|target| Diagnostic::new(Subject::Book, Issue::UnknownTarget(target.clone()))),
+        );
+        diagnostics
+    }
+
     pub fn spawn_sequencers(&self, journal: &Journal) -> Vec<(Blueprint, Sequencer)> {
         self.blueprints
             .iter()
             .map(|bp| (bp.clone(), Sequencer::from(bp, journal)))
             .collect()
     }
+}
 
-    pub fn min_fwd_delta_chrono(&self, ts: DateTime<Local>) -> Option<TimeDelta> {
-        self.blueprints
-            .iter()
-            .map(|blueprint| blueprint.preferred_slot().fwd_delta_chrono(ts))
-            .filter(|delta| !delta.is_zero())
-            .min()
+/// Returns the first dependency cycle among `blueprints`, if any.
+///
+/// Dependencies on blueprints that are not around cannot be part of a cycle;
+/// [Book::validate] reports them.
+fn find_cycle(blueprints: &[Blueprint]) -> Option<Vec<String>> {
+    fn visit<'a>(
+        blueprints: &'a [Blueprint],
+        bp: &'a Blueprint,
+        path: &mut Vec<&'a str>,
+        done: &mut Vec<&'a str>,
+    ) -> Option<Vec<String>> {
+        if let Some(idx) = path.iter().position(|id| *id == bp.id()) {
+            let mut cycle: Vec<_> = path[idx..].iter().map(|id| id.to_string()).collect();
+            cycle.push(bp.id().to_string());
+            return Some(cycle);
+        }
+        if done.contains(&bp.id()) {
+            return None;
+        }
+
+        path.push(bp.id());
+        for dependency in bp.dependencies() {
+            if let Some(prerequisite) = blueprints
+                .iter()
+                .find(|other| other.id() == dependency.blueprint_id())
+                && let Some(cycle) = visit(blueprints, prerequisite, path, done)
+            {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        done.push(bp.id());
+        None
     }
+
+    let mut done = Vec::new();
+    blueprints
+        .iter()
+        .find_map(|bp| visit(blueprints, bp, &mut Vec::new(), &mut done))
 }
 
 impl std::fmt::Display for Book {
@@ -52,48 +264,162 @@ impl std::fmt::Display for Book {
 
 #[cfg(test)]
 mod test {
-    use chrono::TimeDelta;
 
+    use crate::test::blueprint;
     use crate::test::d;
-    use crate::types::Blueprint;
-    use crate::types::Duration;
+    use crate::types::Dependency;
+    use crate::types::Diagnostic;
     use crate::types::HourSlot;
+    use crate::types::Issue;
     use crate::types::Priority;
     use crate::types::Recurrence;
     use crate::types::Slot;
-    use crate::types::TimeUnit;
+    use crate::types::Subject;
+    use crate::types::experimental::anti_affinity::AntiAffinity;
+    use crate::types::experimental::anti_affinity::Separation;
+    use crate::types::experimental::anti_affinity::Target;
+    use crate::types::experimental::blackout::Blackout;
     use crate::types::experimental::book::Book;
+    use crate::types::experimental::book::BookError;
 
     #[test]
-    fn test_min_fwd_delta_chrono() {
-        let eight_am = Slot::Hour(HourSlot::Fixed { hour: 8 });
-        let morning = Slot::Hour(HourSlot::Range { start: 8, stop: 12 });
-        let daily = Recurrence::Period {
-            spacing: Duration::of(1, TimeUnit::Day),
+    fn test_rejects_cycles() {
+        let chore = |id: &str, after: &[&str]| {
+            after.iter().fold(
+                blueprint(
+                    id,
+                    Priority::Norm,
+                    Recurrence::Once,
+                    Slot::Hour(HourSlot::Fixed { hour: 8 }),
+                ),
+                |bp, after| bp.with_dependency(Dependency::new(after.to_string())),
+            )
         };
 
-        let one_hour = Duration::of(1, TimeUnit::Hour);
+        let sut = Book::new(vec![
+            chore("kit", &[]),
+            chore("wash", &["kit"]),
+            chore("wax", &["wash", "kit"]),
+        ]);
+        assert!(sut.is_ok());
+
+        let sut = Book::new(vec![chore("kit", &["wax"]), chore("wash", &["kit"])]).unwrap();
+        assert_eq!(
+            vec![Diagnostic::new(
+                Subject::Blueprint("kit".to_string()),
+                Issue::UnknownDependency("wax".to_string())
+            )],
+            sut.validate()
+        );
 
         let sut = Book::new(vec![
-            Blueprint::new(
-                "1".to_string(),
-                "Task A".to_string(),
-                one_hour,
-                Priority::Crit,
-                daily,
-                eight_am,
-            ),
-            Blueprint::new(
-                "2".to_string(),
-                "Task B".to_string(),
-                one_hour,
-                Priority::Norm,
-                daily,
-                morning,
-            ),
+            chore("kit", &["wax"]),
+            chore("wash", &["kit"]),
+            chore("wax", &["wash"]),
         ]);
+        assert_eq!(
+            Err(BookError::Cycle(vec![
+                "kit".to_string(),
+                "wax".to_string(),
+                "wash".to_string(),
+                "kit".to_string(),
+            ])),
+            sut
+        );
+        assert_eq!(
+            "dependency cycle: kit -> wax -> wash -> kit",
+            sut.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let chore = |id: &str, slot: HourSlot| {
+            blueprint(id, Priority::Norm, Recurrence::Once, Slot::Hour(slot))
+        };
+
+        let sut = Book::new(vec![
+            chore("kit", HourSlot::Fixed { hour: 8 }),
+            chore("wash", HourSlot::Fixed { hour: 24 }),
+            chore("kit", HourSlot::Fixed { hour: 9 }),
+            chore("kit", HourSlot::Fixed { hour: 10 }),
+        ])
+        .unwrap();
+        assert_eq!(
+            vec![
+                Diagnostic::new(
+                    Subject::Blueprint("wash".to_string()),
+                    Issue::InvalidHour(24)
+                ),
+                Diagnostic::new(Subject::Blueprint("kit".to_string()), Issue::DuplicateId),
+            ],
+            sut.validate()
+        );
 
-        let ts = d(2025, 10, 23, 14, 0, 0);
-        assert_eq!(Some(TimeDelta::hours(18)), sut.min_fwd_delta_chrono(ts));
+        let sut = Book::new(vec![
+            chore("kit", HourSlot::Fixed { hour: 8 })
+                .with_dependency(Dependency::new("missing".to_string())),
+        ])
+        .unwrap();
+        assert_eq!(
+            vec![Diagnostic::new(
+                Subject::Blueprint("kit".to_string()),
+                Issue::UnknownDependency("missing".to_string())
+            )],
+            sut.validate()
+        );
+
+        let sut = Book::new(vec![chore("kit", HourSlot::Fixed { hour: 8 })])
+            .unwrap()
+            .with_blackout(Blackout::new(
+                "trip".to_string(),
+                d(2025, 10, 20, 0, 0, 0),
+                d(2025, 10, 27, 0, 0, 0),
+            ))
+            .with_blackout(Blackout::new(
+                "typo".to_string(),
+                d(2025, 10, 27, 0, 0, 0),
+                d(2025, 10, 20, 0, 0, 0),
+            ))
+            .with_blackout(Blackout::new(
+                "void".to_string(),
+                d(2025, 10, 20, 0, 0, 0),
+                d(2025, 10, 20, 0, 0, 0),
+            ));
+        assert_eq!(
+            vec![
+                Diagnostic::new(Subject::Blackout("typo".to_string()), Issue::EmptyBlackout),
+                Diagnostic::new(Subject::Blackout("void".to_string()), Issue::EmptyBlackout),
+            ],
+            sut.validate()
+        );
+
+        let sut = Book::new(vec![
+            chore("kit", HourSlot::Fixed { hour: 8 }).with_group("outdoor".to_string()),
+        ])
+        .unwrap()
+        .with_anti_affinity(AntiAffinity::new(
+            Target::Group("outdoor".to_string()),
+            Target::Blueprint("wsah".to_string()),
+            Separation::Day,
+        ))
+        .with_anti_affinity(AntiAffinity::new(
+            Target::Blueprint("wsah".to_string()),
+            Target::Group("indoor".to_string()),
+            Separation::Day,
+        ));
+        assert_eq!(
+            vec![
+                Diagnostic::new(
+                    Subject::Book,
+                    Issue::UnknownTarget(Target::Blueprint("wsah".to_string()))
+                ),
+                Diagnostic::new(
+                    Subject::Book,
+                    Issue::UnknownTarget(Target::Group("indoor".to_string()))
+                ),
+            ],
+            sut.validate()
+        );
     }
 }
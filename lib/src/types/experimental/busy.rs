@@ -0,0 +1,49 @@
+use chrono::DateTime;
+use chrono::Local;
+
+/// A period of time `[start, end)` that is already taken outside of the
+/// [Book](crate::types::experimental::book::Book), e.g. by a meeting in
+/// another calendar.
+///
+/// Busy time is read-only: nothing gets planned over it, and it is never
+/// moved.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Busy {
+    summary: String,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+}
+
+impl Busy {
+    pub const fn new(summary: String, start: DateTime<Local>, end: DateTime<Local>) -> Self {
+        Self {
+            summary,
+            start,
+            end,
+        }
+    }
+
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    pub const fn start(&self) -> DateTime<Local> {
+        self.start
+    }
+
+    pub const fn end(&self) -> DateTime<Local> {
+        self.end
+    }
+}
+
+impl std::fmt::Display for Busy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.start.to_rfc3339(),
+            self.end.to_rfc3339(),
+            self.summary
+        )
+    }
+}
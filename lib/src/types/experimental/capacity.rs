@@ -0,0 +1,85 @@
+use crate::types::DayOfWeek;
+use crate::types::Duration;
+
+/// Limits the total duration of work that may be planned per day, and per
+/// week.
+///
+/// A limit for a specific day of the week overrides the daily limit on that
+/// day. Missing limits mean there is no limit at all.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Capacity {
+    daily: Option<Duration>,
+    weekly: Option<Duration>,
+    weekdays: [Option<Duration>; 7],
+}
+
+impl Capacity {
+    /// Returns a capacity without any limit.
+    pub const fn unlimited() -> Self {
+        Self {
+            daily: None,
+            weekly: None,
+            weekdays: [None; 7],
+        }
+    }
+
+    /// Limits the planned work of every day.
+    pub const fn with_daily(mut self, limit: Duration) -> Self {
+        self.daily = Some(limit);
+        self
+    }
+
+    /// Limits the planned work of every week, from Monday to Sunday.
+    pub const fn with_weekly(mut self, limit: Duration) -> Self {
+        self.weekly = Some(limit);
+        self
+    }
+
+    /// Limits the planned work of a specific day of the week.
+    pub const fn with_weekday(mut self, day: DayOfWeek, limit: Duration) -> Self {
+        self.weekdays[day as usize] = Some(limit);
+        self
+    }
+
+    /// Returns the limit that applies to `day`, if any.
+    pub const fn daily_limit(&self, day: DayOfWeek) -> Option<Duration> {
+        match self.weekdays[day as usize] {
+            Some(limit) => Some(limit),
+            None => self.daily,
+        }
+    }
+
+    /// Returns the limit that applies to a whole week, if any.
+    pub const fn weekly_limit(&self) -> Option<Duration> {
+        self.weekly
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_daily_limit() {
+        let sut = Capacity::unlimited();
+        assert_eq!(None, sut.daily_limit(DayOfWeek::Mon));
+
+        let sut = Capacity::unlimited()
+            .with_daily(Duration::hours(2))
+            .with_weekday(DayOfWeek::Sat, Duration::hours(4));
+        assert_eq!(Some(Duration::hours(2)), sut.daily_limit(DayOfWeek::Mon));
+        assert_eq!(Some(Duration::hours(4)), sut.daily_limit(DayOfWeek::Sat));
+
+        let sut = Capacity::unlimited().with_weekday(DayOfWeek::Sun, Duration::hours(1));
+        assert_eq!(None, sut.daily_limit(DayOfWeek::Sat));
+        assert_eq!(Some(Duration::hours(1)), sut.daily_limit(DayOfWeek::Sun));
+    }
+
+    #[test]
+    fn test_weekly_limit() {
+        let sut = Capacity::unlimited().with_weekly(Duration::hours(6));
+        assert_eq!(Some(Duration::hours(6)), sut.weekly_limit());
+        assert_eq!(None, sut.daily_limit(DayOfWeek::Mon));
+    }
+}
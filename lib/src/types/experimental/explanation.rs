@@ -0,0 +1,206 @@
+use chrono::DateTime;
+use chrono::Local;
+
+/// Why the planner placed the occurrences of a blueprint where it did, or
+/// why it could not place them.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Explanation {
+    blueprint_id: String,
+    attempts: Vec<Attempt>,
+    decision: Decision,
+}
+
+impl Explanation {
+    pub const fn new(blueprint_id: String, attempts: Vec<Attempt>, decision: Decision) -> Self {
+        Self {
+            blueprint_id,
+            attempts,
+            decision,
+        }
+    }
+
+    pub fn blueprint_id(&self) -> &str {
+        &self.blueprint_id
+    }
+
+    /// Returns the candidates that were considered, in the order they were
+    /// considered.
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    pub const fn decision(&self) -> &Decision {
+        &self.decision
+    }
+}
+
+impl std::fmt::Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}: {}", self.blueprint_id, self.decision)?;
+        for attempt in &self.attempts {
+            writeln!(f, "  {attempt}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A candidate timestamp for the next occurrence of a blueprint, and the
+/// constraint that rejected it, if any.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Attempt {
+    candidate: DateTime<Local>,
+    rejection: Option<Rejection>,
+}
+
+impl Attempt {
+    pub const fn placed(candidate: DateTime<Local>) -> Self {
+        Self {
+            candidate,
+            rejection: None,
+        }
+    }
+
+    pub const fn rejected(candidate: DateTime<Local>, rejection: Rejection) -> Self {
+        Self {
+            candidate,
+            rejection: Some(rejection),
+        }
+    }
+
+    pub const fn candidate(&self) -> DateTime<Local> {
+        self.candidate
+    }
+
+    pub const fn rejection(&self) -> Option<&Rejection> {
+        self.rejection.as_ref()
+    }
+}
+
+impl std::fmt::Display for Attempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.rejection {
+            Some(rejection) => write!(f, "{} {}", self.candidate.to_rfc3339(), rejection),
+            None => write!(f, "{} placed", self.candidate.to_rfc3339()),
+        }
+    }
+}
+
+/// The constraint that rejected a candidate.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Rejection {
+    /// The occurrence does not fit within the slot, or the window of the
+    /// recurrence.
+    Slot,
+
+    /// The occurrence is left to a pin that comes before the sequence
+    /// accepts the next one.
+    Pin,
+
+    /// The prerequisite with the given blueprint id is not done by then.
+    Prerequisite(String),
+
+    /// The occurrence is kept apart from the entries of the blueprint with
+    /// the given id.
+    AntiAffinity(String),
+
+    /// The occurrence falls within a blackout of the book.
+    Blackout,
+
+    /// The occurrence takes busy time of another calendar.
+    Busy,
+
+    /// The time is taken by an entry of the blueprint with the given id,
+    /// which is pinned, or does not have a lower priority.
+    Conflict(String),
+
+    /// The occurrence exceeds the capacity of the book.
+    Capacity,
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Slot => f.write_str("outside of its slot"),
+            Rejection::Pin => f.write_str("left to a pin"),
+            Rejection::Prerequisite(id) => write!(f, "waiting for {id}"),
+            Rejection::AntiAffinity(id) => write!(f, "kept apart from {id}"),
+            Rejection::Blackout => f.write_str("blacked out"),
+            Rejection::Busy => f.write_str("busy"),
+            Rejection::Conflict(id) => write!(f, "taken by {id}"),
+            Rejection::Capacity => f.write_str("over capacity"),
+        }
+    }
+}
+
+/// What the planner ended up doing with a blueprint.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Decision {
+    /// The given number of occurrences got planned.
+    Planned(usize),
+
+    /// The blueprint is paused.
+    Paused,
+
+    /// There are no occurrences left, e.g. after all of its times.
+    Exhausted,
+
+    /// The active period of the blueprint is over.
+    Inactive,
+
+    /// The window of the next occurrence closed before any candidate came
+    /// up.
+    Closed,
+
+    /// The next occurrence is not due until the given time, which is past
+    /// the end of the plan.
+    NotDue(DateTime<Local>),
+
+    /// Every candidate was rejected.
+    Rejected,
+}
+
+impl std::fmt::Display for Decision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Decision::Planned(count) => write!(f, "planned {count}"),
+            Decision::Paused => f.write_str("paused"),
+            Decision::Exhausted => f.write_str("no occurrences left"),
+            Decision::Inactive => f.write_str("inactive"),
+            Decision::Closed => f.write_str("window closed"),
+            Decision::NotDue(ts) => write!(f, "not due until {}", ts.to_rfc3339()),
+            Decision::Rejected => f.write_str("every candidate rejected"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::test::d;
+
+    #[test]
+    fn test_display() {
+        let sut = Explanation::new(
+            "gym".to_string(),
+            vec![
+                Attempt::rejected(
+                    d(2025, 10, 23, 9, 0, 0),
+                    Rejection::Conflict("dentist".to_string()),
+                ),
+                Attempt::placed(d(2025, 10, 23, 10, 0, 0)),
+            ],
+            Decision::Planned(1),
+        );
+        assert_eq!(
+            format!(
+                "gym: planned 1\n  {} taken by dentist\n  {} placed\n",
+                d(2025, 10, 23, 9, 0, 0).to_rfc3339(),
+                d(2025, 10, 23, 10, 0, 0).to_rfc3339()
+            ),
+            sut.to_string()
+        );
+    }
+}
@@ -1,10 +1,46 @@
 use chrono::DateTime;
 use chrono::Local;
+use chrono::TimeDelta;
+
+use crate::types::Duration;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Action {
     Completed,
-    Postponed,
+    Postponed(Postpone),
+
+    /// Part of the pending occurrence was done, such as a chunk of a
+    /// splittable blueprint.
+    Progressed(Duration),
+}
+
+/// How far a pending occurrence is postponed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Postpone {
+    /// Not before the given time.
+    Until(DateTime<Local>),
+
+    /// By a fixed amount of time, from when it was postponed.
+    By(Duration),
+
+    /// By one day from when it was postponed, doubling with every
+    /// consecutive postponement of the same occurrence.
+    Backoff,
+}
+
+impl Postpone {
+    /// Returns the earliest time the occurrence may take place, once
+    /// postponed at `committed_at` for the `times`-th time in a row.
+    pub fn not_before(self, committed_at: DateTime<Local>, times: usize) -> DateTime<Local> {
+        match self {
+            Postpone::Until(ts) => ts,
+            Postpone::By(duration) => committed_at + duration.timedelta(),
+            Postpone::Backoff => {
+                let exponent = times.saturating_sub(1).min(16) as u32;
+                committed_at + TimeDelta::days(1 << exponent)
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -27,8 +63,20 @@ impl Commit {
         Self::new(blueprint_id, committed_at, Action::Completed)
     }
 
-    pub const fn postponed(blueprint_id: String, committed_at: DateTime<Local>) -> Self {
-        Self::new(blueprint_id, committed_at, Action::Postponed)
+    pub const fn postponed(
+        blueprint_id: String,
+        committed_at: DateTime<Local>,
+        postpone: Postpone,
+    ) -> Self {
+        Self::new(blueprint_id, committed_at, Action::Postponed(postpone))
+    }
+
+    pub const fn progressed(
+        blueprint_id: String,
+        committed_at: DateTime<Local>,
+        duration: Duration,
+    ) -> Self {
+        Self::new(blueprint_id, committed_at, Action::Progressed(duration))
     }
 
     pub fn blueprint_id(&self) -> &str {
@@ -44,6 +92,7 @@ impl Commit {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Journal {
     commits: Vec<Commit>,
 }
@@ -57,6 +106,17 @@ impl Journal {
         &self.commits
     }
 
+    /// Appends `commit`, which must not be older than the last one.
+    pub fn record(&mut self, commit: Commit) {
+        debug_assert!(
+            self.commits
+                .last()
+                .is_none_or(|last| last.committed_at <= commit.committed_at),
+            "commits must be recorded in order"
+        );
+        self.commits.push(commit);
+    }
+
     pub fn get_last_commit_for(&self, blueprint_id: &str) -> Option<&Commit> {
         self.commits
             .iter()
@@ -64,6 +124,57 @@ impl Journal {
             .find(|commit| commit.blueprint_id == blueprint_id)
     }
 
+    /// Returns the postponements of the pending occurrence of the blueprint,
+    /// that is, those in a row since it was last worked on.
+    pub fn get_postponements_for(&self, blueprint_id: &str) -> Vec<&Commit> {
+        let mut postponements: Vec<_> = self
+            .commits
+            .iter()
+            .rev()
+            .filter(|commit| commit.blueprint_id == blueprint_id)
+            .take_while(|commit| matches!(commit.action, Action::Postponed(_)))
+            .collect();
+        postponements.reverse();
+        postponements
+    }
+
+    /// Returns the commits that completed an occurrence of the blueprint,
+    /// which takes `duration`: either it was completed explicitly, or the
+    /// work done on it reached `duration`.
+    pub fn get_completions_for(&self, blueprint_id: &str, duration: Duration) -> Vec<&Commit> {
+        self.settle(blueprint_id, duration).0
+    }
+
+    /// Returns the work done on the pending occurrence of the blueprint,
+    /// which takes `duration`.
+    pub fn get_progress_for(&self, blueprint_id: &str, duration: Duration) -> TimeDelta {
+        self.settle(blueprint_id, duration).1
+    }
+
+    fn settle(&self, blueprint_id: &str, duration: Duration) -> (Vec<&Commit>, TimeDelta) {
+        let mut completions = Vec::new();
+        let mut progress = TimeDelta::zero();
+        for commit in self
+            .commits
+            .iter()
+            .filter(|commit| commit.blueprint_id == blueprint_id)
+        {
+            match commit.action {
+                Action::Completed => {}
+                Action::Postponed(_) => continue,
+                Action::Progressed(done) => {
+                    progress += done.timedelta();
+                    if progress < duration.timedelta() {
+                        continue;
+                    }
+                }
+            }
+            completions.push(commit);
+            progress = TimeDelta::zero();
+        }
+        (completions, progress)
+    }
+
     pub fn get_last_commit(&self) -> Option<&Commit> {
         self.commits.last()
     }
@@ -89,4 +200,72 @@ mod test {
         assert_eq!(Some(&commit), sut.get_last_commit_for("found"));
         assert_eq!(sut.get_last_commit_for("found"), sut.get_last_commit());
     }
+
+    #[test]
+    fn test_get_postponements_for() {
+        let sut = Journal::new(vec![
+            Commit::postponed("a".into(), d(2025, 10, 1, 0, 0, 0), Postpone::Backoff),
+            Commit::completed("a".into(), d(2025, 10, 2, 0, 0, 0)),
+            Commit::postponed("a".into(), d(2025, 10, 3, 0, 0, 0), Postpone::Backoff),
+            Commit::completed("b".into(), d(2025, 10, 4, 0, 0, 0)),
+            Commit::postponed("a".into(), d(2025, 10, 5, 0, 0, 0), Postpone::Backoff),
+        ]);
+
+        let postponed_at: Vec<_> = sut
+            .get_postponements_for("a")
+            .iter()
+            .map(|commit| commit.committed_at())
+            .collect();
+        assert_eq!(
+            vec![d(2025, 10, 3, 0, 0, 0), d(2025, 10, 5, 0, 0, 0)],
+            postponed_at
+        );
+        assert!(sut.get_postponements_for("b").is_empty());
+    }
+
+    #[test]
+    fn test_get_completions_for() {
+        let sut = Journal::new(vec![
+            Commit::progressed("a".into(), d(2025, 10, 1, 0, 0, 0), Duration::hours(2)),
+            Commit::progressed("a".into(), d(2025, 10, 2, 0, 0, 0), Duration::hours(4)),
+            Commit::progressed("a".into(), d(2025, 10, 3, 0, 0, 0), Duration::hours(3)),
+            Commit::completed("a".into(), d(2025, 10, 4, 0, 0, 0)),
+            Commit::progressed("a".into(), d(2025, 10, 5, 0, 0, 0), Duration::hours(1)),
+        ]);
+
+        let completed_at: Vec<_> = sut
+            .get_completions_for("a", Duration::hours(6))
+            .iter()
+            .map(|commit| commit.committed_at())
+            .collect();
+        assert_eq!(
+            vec![d(2025, 10, 2, 0, 0, 0), d(2025, 10, 4, 0, 0, 0)],
+            completed_at
+        );
+        assert_eq!(
+            TimeDelta::hours(1),
+            sut.get_progress_for("a", Duration::hours(6))
+        );
+        assert_eq!(
+            TimeDelta::zero(),
+            sut.get_progress_for("b", Duration::hours(6))
+        );
+    }
+
+    #[test]
+    fn test_not_before() {
+        let ts = d(2025, 10, 23, 14, 0, 0);
+
+        let sut = Postpone::Until(d(2025, 11, 1, 0, 0, 0));
+        assert_eq!(d(2025, 11, 1, 0, 0, 0), sut.not_before(ts, 3));
+
+        let sut = Postpone::By(Duration::days(2));
+        assert_eq!(ts + TimeDelta::days(2), sut.not_before(ts, 1));
+        assert_eq!(ts + TimeDelta::days(2), sut.not_before(ts, 3));
+
+        let sut = Postpone::Backoff;
+        assert_eq!(ts + TimeDelta::days(1), sut.not_before(ts, 1));
+        assert_eq!(ts + TimeDelta::days(2), sut.not_before(ts, 2));
+        assert_eq!(ts + TimeDelta::days(4), sut.not_before(ts, 3));
+    }
 }
@@ -0,0 +1,80 @@
+use chrono::DateTime;
+use chrono::Local;
+
+/// Models an occurrence of a blueprint that was fixed at a given time by
+/// hand.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pin {
+    blueprint_id: String,
+    pinned_for: DateTime<Local>,
+}
+
+impl Pin {
+    pub const fn new(blueprint_id: String, pinned_for: DateTime<Local>) -> Self {
+        Self {
+            blueprint_id,
+            pinned_for,
+        }
+    }
+
+    pub fn blueprint_id(&self) -> &str {
+        &self.blueprint_id
+    }
+
+    pub const fn pinned_for(&self) -> DateTime<Local> {
+        self.pinned_for
+    }
+}
+
+impl std::fmt::Display for Pin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.blueprint_id, self.pinned_for.to_rfc3339())
+    }
+}
+
+/// Keeps the pins set by hand, which are stored next to the
+/// [Journal](crate::types::experimental::journal::Journal) and read by
+/// [Document::pins](crate::data::Document::pins).
+///
+/// Pins are ordered by time.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pins {
+    pins: Vec<Pin>,
+}
+
+impl Pins {
+    pub fn new(mut pins: Vec<Pin>) -> Self {
+        pins.sort_by_key(Pin::pinned_for);
+        Self { pins }
+    }
+
+    pub fn pins(&self) -> &[Pin] {
+        &self.pins
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::test::d;
+
+    #[test]
+    fn test_new() {
+        let sut = Pins::new(vec![
+            Pin::new("a".into(), d(2025, 10, 25, 10, 0, 0)),
+            Pin::new("b".into(), d(2025, 10, 24, 10, 0, 0)),
+            Pin::new("a".into(), d(2025, 10, 23, 10, 0, 0)),
+        ]);
+
+        let pinned_for: Vec<_> = sut.pins().iter().map(Pin::pinned_for).collect();
+        assert_eq!(
+            vec![
+                d(2025, 10, 23, 10, 0, 0),
+                d(2025, 10, 24, 10, 0, 0),
+                d(2025, 10, 25, 10, 0, 0)
+            ],
+            pinned_for
+        );
+    }
+}
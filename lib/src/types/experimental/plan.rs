@@ -1,25 +1,430 @@
+use chrono::DateTime;
+use chrono::Local;
+
+use crate::types::CatchUp;
+use crate::types::Priority;
+use crate::types::experimental::explanation::Explanation;
+use crate::types::experimental::pins::Pin;
 use crate::types::experimental::plan_entry::PlanEntry;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Plan {
     entries: Vec<PlanEntry>,
+    displaced: Vec<Displacement>,
+    at_risk: Vec<AtRisk>,
+    overdue: Vec<Overdue>,
+    stale_pins: Vec<StalePin>,
+    moved: usize,
+    explanations: Vec<Explanation>,
 }
 
 impl Plan {
     pub fn new(entries: Vec<PlanEntry>) -> Self {
-        Self { entries }
+        Self {
+            entries,
+            displaced: Vec::new(),
+            at_risk: Vec::new(),
+            overdue: Vec::new(),
+            stale_pins: Vec::new(),
+            moved: 0,
+            explanations: Vec::new(),
+        }
+    }
+
+    pub fn with_displaced(mut self, displaced: Vec<Displacement>) -> Self {
+        self.displaced = displaced;
+        self
+    }
+
+    pub fn with_at_risk(mut self, at_risk: Vec<AtRisk>) -> Self {
+        self.at_risk = at_risk;
+        self
+    }
+
+    pub fn with_overdue(mut self, overdue: Vec<Overdue>) -> Self {
+        self.overdue = overdue;
+        self
+    }
+
+    pub fn with_stale_pins(mut self, stale_pins: Vec<StalePin>) -> Self {
+        self.stale_pins = stale_pins;
+        self
+    }
+
+    pub const fn with_moved(mut self, moved: usize) -> Self {
+        self.moved = moved;
+        self
+    }
+
+    pub fn with_explanations(mut self, explanations: Vec<Explanation>) -> Self {
+        self.explanations = explanations;
+        self
+    }
+
+    pub fn entries(&self) -> &[PlanEntry] {
+        &self.entries
     }
 
+    /// Returns the entries that were removed from the plan while planning.
+    pub fn displaced(&self) -> &[Displacement] {
+        &self.displaced
+    }
+
+    /// Returns the occurrences whose deadline is not met by the plan.
+    pub fn at_risk(&self) -> &[AtRisk] {
+        &self.at_risk
+    }
+
+    /// Returns the blueprints that were overdue when planning started.
+    pub fn overdue(&self) -> &[Overdue] {
+        &self.overdue
+    }
+
+    /// Returns why each blueprint was planned the way it was, when the plan
+    /// was explained.
+    pub fn explanations(&self) -> &[Explanation] {
+        &self.explanations
+    }
+
+    /// Returns the explanation for the blueprint with the given id.
+    pub fn explanation_for(&self, blueprint_id: &str) -> Option<&Explanation> {
+        self.explanations
+            .iter()
+            .find(|explanation| explanation.blueprint_id() == blueprint_id)
+    }
+
+    /// Returns the pins that were left out of the plan.
+    pub fn stale_pins(&self) -> &[StalePin] {
+        &self.stale_pins
+    }
+
+    /// Returns how many entries of the previous plan were not kept as they
+    /// were.
+    pub const fn moved(&self) -> usize {
+        self.moved
+    }
+
+    /// Renders one line per entry. Buffers get a line of their own, right
+    /// before and after their entry, with when they start and how long they
+    /// take.
     pub fn as_str(&self) -> String {
         let mut out = String::new();
         for entry in self.entries.iter() {
+            if let Some(before) = entry.buffer_before() {
+                let line = format!(
+                    "{:1} buffer {} {}\n",
+                    entry.blueprint_id(),
+                    (entry.planned_for() - before.timedelta()).to_rfc3339(),
+                    before
+                );
+                out.push_str(&line);
+            }
             let line = format!(
                 "{:1} {}\n",
                 entry.blueprint_id(),
                 entry.planned_for().to_rfc3339()
             );
             out.push_str(&line);
+            if let Some(after) = entry.buffer_after() {
+                let line = format!(
+                    "{:1} buffer {} {}\n",
+                    entry.blueprint_id(),
+                    (entry.planned_for() + entry.duration().timedelta()).to_rfc3339(),
+                    after
+                );
+                out.push_str(&line);
+            }
         }
         out
     }
 }
+
+/// Models a [PlanEntry] that was taken out of the plan, and why.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Displacement {
+    entry: PlanEntry,
+    reason: Reason,
+}
+
+impl Displacement {
+    pub const fn new(entry: PlanEntry, reason: Reason) -> Self {
+        Self { entry, reason }
+    }
+
+    pub const fn entry(&self) -> &PlanEntry {
+        &self.entry
+    }
+
+    pub const fn reason(&self) -> &Reason {
+        &self.reason
+    }
+}
+
+impl std::fmt::Display for Displacement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.entry.blueprint_id(),
+            self.entry.planned_for().to_rfc3339(),
+            self.reason
+        )
+    }
+}
+
+/// Models an occurrence that is planned to end after its deadline, or that
+/// could not be planned at all before it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AtRisk {
+    blueprint_id: String,
+    deadline: DateTime<Local>,
+    planned_for: Option<DateTime<Local>>,
+}
+
+impl AtRisk {
+    pub const fn new(
+        blueprint_id: String,
+        deadline: DateTime<Local>,
+        planned_for: Option<DateTime<Local>>,
+    ) -> Self {
+        Self {
+            blueprint_id,
+            deadline,
+            planned_for,
+        }
+    }
+
+    pub fn blueprint_id(&self) -> &str {
+        &self.blueprint_id
+    }
+
+    pub const fn deadline(&self) -> DateTime<Local> {
+        self.deadline
+    }
+
+    /// Returns when the occurrence is planned for, or `None` if it could not
+    /// be planned.
+    pub const fn planned_for(&self) -> Option<DateTime<Local>> {
+        self.planned_for
+    }
+}
+
+impl std::fmt::Display for AtRisk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} due {}",
+            self.blueprint_id,
+            self.deadline.to_rfc3339()
+        )?;
+        match self.planned_for {
+            Some(ts) => write!(f, " planned for {}", ts.to_rfc3339()),
+            None => f.write_str(" unplanned"),
+        }
+    }
+}
+
+/// Models the occurrences of a blueprint that were overdue when planning
+/// started, and how the plan catches up with them.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Overdue {
+    blueprint_id: String,
+    missed: usize,
+    catch_up: CatchUp,
+    planned: usize,
+}
+
+impl Overdue {
+    pub const fn new(
+        blueprint_id: String,
+        missed: usize,
+        catch_up: CatchUp,
+        planned: usize,
+    ) -> Self {
+        Self {
+            blueprint_id,
+            missed,
+            catch_up,
+            planned,
+        }
+    }
+
+    pub fn blueprint_id(&self) -> &str {
+        &self.blueprint_id
+    }
+
+    /// Returns how many occurrences were missed.
+    pub const fn missed(&self) -> usize {
+        self.missed
+    }
+
+    pub const fn catch_up(&self) -> CatchUp {
+        self.catch_up
+    }
+
+    /// Returns how many catch up occurrences made it into the plan.
+    pub const fn planned(&self) -> usize {
+        self.planned
+    }
+}
+
+impl std::fmt::Display for Overdue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} missed {} catch up {} planned {}",
+            self.blueprint_id, self.missed, self.catch_up, self.planned
+        )
+    }
+}
+
+/// Models a [Pin] that was left out of the plan, and why.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StalePin {
+    pin: Pin,
+    staleness: Staleness,
+}
+
+impl StalePin {
+    pub const fn new(pin: Pin, staleness: Staleness) -> Self {
+        Self { pin, staleness }
+    }
+
+    pub const fn pin(&self) -> &Pin {
+        &self.pin
+    }
+
+    pub const fn staleness(&self) -> Staleness {
+        self.staleness
+    }
+}
+
+impl std::fmt::Display for StalePin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.pin, self.staleness)
+    }
+}
+
+/// The reason why a pin is stale.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Staleness {
+    /// The blueprint is no longer in the book.
+    Deleted,
+
+    /// The pinned time is already in the past.
+    Past,
+
+    /// Another pin already takes the span.
+    Overlapped,
+
+    /// The span falls within a blackout of the book.
+    Blackout,
+
+    /// The span takes busy time of another calendar.
+    Busy,
+}
+
+impl Staleness {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Staleness::Deleted => "deleted",
+            Staleness::Past => "past",
+            Staleness::Overlapped => "overlapped",
+            Staleness::Blackout => "blacked out",
+            Staleness::Busy => "busy",
+        }
+    }
+}
+
+impl std::fmt::Display for Staleness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The reason why an entry was displaced.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Reason {
+    /// Another blueprint with higher priority took over the span.
+    Preempted { by: String, priority: Priority },
+}
+
+impl std::fmt::Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::Preempted { by, priority } => write!(f, "preempted by {by} ({priority})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::test::d;
+    use crate::types::Duration;
+
+    #[test]
+    fn test_reason_display() {
+        let sut = Reason::Preempted {
+            by: "dentist".to_string(),
+            priority: Priority::Crit,
+        };
+        assert_eq!("preempted by dentist (CRIT)", sut.to_string());
+    }
+
+    #[test]
+    fn test_overdue_display() {
+        let sut = Overdue::new("plants".to_string(), 14, CatchUp::Once, 1);
+        assert_eq!("plants missed 14 catch up once planned 1", sut.to_string());
+    }
+
+    #[test]
+    fn test_stale_pin_display() {
+        let sut = StalePin::new(
+            Pin::new("dentist".to_string(), d(2025, 10, 25, 10, 0, 0)),
+            Staleness::Past,
+        );
+        assert_eq!(
+            format!("dentist {} past", d(2025, 10, 25, 10, 0, 0).to_rfc3339()),
+            sut.to_string()
+        );
+    }
+
+    #[test]
+    fn test_as_str_with_buffers() {
+        let ts = d(2025, 10, 23, 10, 0, 0);
+        let sut = Plan::new(vec![
+            PlanEntry::new("errand".to_string(), Duration::hours(1), ts)
+                .with_buffers(Some(Duration::minutes(30)), Some(Duration::minutes(15))),
+        ]);
+
+        assert_eq!(
+            format!(
+                "errand buffer {} 30min\nerrand {}\nerrand buffer {} 15min\n",
+                d(2025, 10, 23, 9, 30, 0).to_rfc3339(),
+                ts.to_rfc3339(),
+                d(2025, 10, 23, 11, 0, 0).to_rfc3339(),
+            ),
+            sut.as_str()
+        );
+    }
+
+    #[test]
+    fn test_displaced() {
+        let displacement = Displacement::new(
+            PlanEntry::new(
+                "vac".to_string(),
+                Duration::hours(1),
+                d(2025, 10, 25, 10, 0, 0),
+            ),
+            Reason::Preempted {
+                by: "dentist".to_string(),
+                priority: Priority::Crit,
+            },
+        );
+        let sut = Plan::new(vec![]).with_displaced(vec![displacement.clone()]);
+
+        assert_eq!(&[displacement], sut.displaced());
+    }
+}
@@ -9,6 +9,8 @@ pub struct PlanEntry {
     blueprint_id: String,
     planned_for: DateTime<Local>,
     duration: Duration,
+    buffer_before: Option<Duration>,
+    buffer_after: Option<Duration>,
 }
 
 impl PlanEntry {
@@ -21,9 +23,18 @@ impl PlanEntry {
             blueprint_id,
             planned_for,
             duration,
+            buffer_before: None,
+            buffer_after: None,
         }
     }
 
+    /// Sets the time kept free before and after the entry.
+    pub const fn with_buffers(mut self, before: Option<Duration>, after: Option<Duration>) -> Self {
+        self.buffer_before = before;
+        self.buffer_after = after;
+        self
+    }
+
     pub fn blueprint_id(&self) -> &str {
         &self.blueprint_id
     }
@@ -35,4 +46,12 @@ impl PlanEntry {
     pub const fn duration(&self) -> Duration {
         self.duration
     }
+
+    pub const fn buffer_before(&self) -> Option<Duration> {
+        self.buffer_before
+    }
+
+    pub const fn buffer_after(&self) -> Option<Duration> {
+        self.buffer_after
+    }
 }
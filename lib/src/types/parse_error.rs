@@ -0,0 +1,32 @@
+/// The reason why a value cannot be parsed out of a string.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    expected: &'static str,
+    input: String,
+}
+
+impl ParseError {
+    pub fn new(expected: &'static str, input: &str) -> Self {
+        Self {
+            expected,
+            input: input.to_string(),
+        }
+    }
+
+    /// Returns what the input was expected to be, e.g. "duration".
+    pub const fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid {}: {:?}", self.expected, self.input)
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -1,3 +1,5 @@
+use crate::types::ParseError;
+
 /// Priority enumeration.
 /// From most to least priority.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
@@ -9,6 +11,17 @@ pub enum Priority {
 }
 
 impl Priority {
+    /// Returns the priority raised by `steps` levels, up to [Priority::Crit].
+    pub const fn escalated(self, steps: usize) -> Self {
+        let level = self as usize + steps;
+        match level {
+            0 => Priority::Idle,
+            1 => Priority::Norm,
+            2 => Priority::High,
+            _ => Priority::Crit,
+        }
+    }
+
     pub const fn as_str(&self) -> &'static str {
         match self {
             Priority::Idle => "IDLE",
@@ -19,6 +32,20 @@ impl Priority {
     }
 }
 
+impl std::str::FromStr for Priority {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "IDLE" => Ok(Priority::Idle),
+            "NORM" => Ok(Priority::Norm),
+            "HIGH" => Ok(Priority::High),
+            "CRIT" => Ok(Priority::Crit),
+            _ => Err(ParseError::new("priority", s)),
+        }
+    }
+}
+
 impl std::fmt::Display for Priority {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("{}", self.as_str()))
@@ -37,4 +64,12 @@ mod test {
         assert!(Priority::Norm > Priority::Idle);
         assert!(Priority::Idle == Priority::Idle);
     }
+
+    #[test]
+    fn test_escalated() {
+        assert_eq!(Priority::Idle, Priority::Idle.escalated(0));
+        assert_eq!(Priority::Norm, Priority::Idle.escalated(1));
+        assert_eq!(Priority::Crit, Priority::Norm.escalated(2));
+        assert_eq!(Priority::Crit, Priority::High.escalated(5));
+    }
 }
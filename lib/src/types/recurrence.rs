@@ -2,6 +2,7 @@ use chrono::DateTime;
 use chrono::Local;
 
 use crate::types::Duration;
+use crate::types::ParseError;
 
 /// Recurrence of an event.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -20,6 +21,27 @@ pub enum Recurrence {
     /// The event repeats forever, with each occurrence spaced by
     /// `spacing` duration. Does not stop unless explicitly cancelled.
     Period { spacing: Duration },
+
+    /// Repeats indefinitely, within a lead time before each expiry.
+    ///
+    /// Each occurrence expires `period` after it takes place. The next
+    /// occurrence is allowed from `earliest` up to `latest` before that
+    /// expiry, e.g. renew anywhere between 6 and 3 months in advance.
+    Lead {
+        period: Duration,
+        earliest: Duration,
+        latest: Duration,
+    },
+
+    /// Repeats indefinitely, about every `spacing`.
+    ///
+    /// The next occurrence is nominally due `spacing` after the previous one,
+    /// but it is allowed from `early` before up to `late` after that.
+    About {
+        spacing: Duration,
+        early: Duration,
+        late: Duration,
+    },
 }
 
 impl Recurrence {
@@ -31,7 +53,19 @@ impl Recurrence {
         match self {
             Recurrence::Once => Some(1),
             Recurrence::Times { count, .. } => Some(count),
-            Recurrence::Period { .. } => None,
+            Recurrence::Period { .. } | Recurrence::Lead { .. } | Recurrence::About { .. } => None,
+        }
+    }
+
+    /// Returns the regular amount of time between occurrences, or `None` if
+    /// the recurrence does not repeat.
+    pub const fn spacing(self) -> Option<Duration> {
+        match self {
+            Recurrence::Once => None,
+            Recurrence::Times { spacing, .. }
+            | Recurrence::Period { spacing }
+            | Recurrence::About { spacing, .. } => Some(spacing),
+            Recurrence::Lead { period, .. } => Some(period),
         }
     }
 
@@ -42,10 +76,85 @@ impl Recurrence {
             Recurrence::Times { spacing, .. } | Recurrence::Period { spacing } => {
                 ts + spacing.timedelta()
             }
+            Recurrence::Lead {
+                period, earliest, ..
+            } => ts + period.timedelta() - earliest.timedelta(),
+            Recurrence::About { spacing, early, .. } => {
+                ts + spacing.timedelta() - early.timedelta()
+            }
+        }
+    }
+
+    /// Returns the preferred timestamp for the occurrence that follows one at
+    /// `ts`, or `None` when the recurrence has no preference.
+    pub fn nominal(self, ts: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Recurrence::About { spacing, .. } => Some(ts + spacing.timedelta()),
+            _ => None,
+        }
+    }
+
+    /// Returns the latest timestamp allowed for the occurrence that follows
+    /// one at `ts`, or `None` when there is no upper bound.
+    pub fn limit(self, ts: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Recurrence::Lead { period, latest, .. } => {
+                Some(ts + period.timedelta() - latest.timedelta())
+            }
+            Recurrence::About { spacing, late, .. } => {
+                Some(ts + spacing.timedelta() + late.timedelta())
+            }
+            _ => None,
         }
     }
 }
 
+impl std::str::FromStr for Recurrence {
+    type Err = ParseError;
+
+    /// Parses a recurrence, as displayed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseError::new("recurrence", s);
+        let duration = |d: &str| d.parse::<Duration>().map_err(|_| error());
+        // Splits "{spacing}[{a},{b}]" into its parts.
+        fn window(w: &str) -> Option<(&str, &str, &str)> {
+            let (spacing, bounds) = w.strip_suffix(']')?.split_once('[')?;
+            let (a, b) = bounds.split_once(',')?;
+            Some((spacing, a, b))
+        }
+
+        let s = s.strip_prefix('^').ok_or_else(error)?;
+        if s == "1" {
+            return Ok(Recurrence::Once);
+        }
+        if let Some(times) = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            let (count, spacing) = times.split_once(',').ok_or_else(error)?;
+            return Ok(Recurrence::Times {
+                count: count.parse().map_err(|_| error())?,
+                spacing: duration(spacing)?,
+            });
+        }
+        if let Some(about) = s.strip_prefix('~') {
+            let (spacing, early, late) = window(about).ok_or_else(error)?;
+            return Ok(Recurrence::About {
+                spacing: duration(spacing)?,
+                early: duration(early.strip_prefix('-').ok_or_else(error)?)?,
+                late: duration(late.strip_prefix('+').ok_or_else(error)?)?,
+            });
+        }
+        if let Some((period, earliest, latest)) = window(s) {
+            return Ok(Recurrence::Lead {
+                period: duration(period)?,
+                earliest: duration(earliest.strip_prefix('-').ok_or_else(error)?)?,
+                latest: duration(latest.strip_prefix('-').ok_or_else(error)?)?,
+            });
+        }
+        Ok(Recurrence::Period {
+            spacing: duration(s)?,
+        })
+    }
+}
+
 impl std::fmt::Display for Recurrence {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let args = match self {
@@ -54,6 +163,16 @@ impl std::fmt::Display for Recurrence {
                 format_args!("^{{{},{}}}", *count, *spacing)
             }
             Recurrence::Period { spacing } => format_args!("^{}", *spacing),
+            Recurrence::Lead {
+                period,
+                earliest,
+                latest,
+            } => format_args!("^{}[-{},-{}]", *period, *earliest, *latest),
+            Recurrence::About {
+                spacing,
+                early,
+                late,
+            } => format_args!("^~{}[-{},+{}]", *spacing, *early, *late),
         };
         f.write_fmt(args)
     }
@@ -83,6 +202,51 @@ mod test {
             spacing: Duration::of(3, TimeUnit::Year),
         };
         assert_eq!("^3y", sut.to_string());
+
+        let sut = Recurrence::Lead {
+            period: Duration::of(8, TimeUnit::Year),
+            earliest: Duration::of(6, TimeUnit::Month),
+            latest: Duration::of(3, TimeUnit::Month),
+        };
+        assert_eq!("^8y[-6mo,-3mo]", sut.to_string());
+
+        let sut = Recurrence::About {
+            spacing: Duration::of(1, TimeUnit::Year),
+            early: Duration::days(30),
+            late: Duration::days(60),
+        };
+        assert_eq!("^~1y[-30d,+60d]", sut.to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let recurrences = [
+            Recurrence::Once,
+            Recurrence::Times {
+                count: 3,
+                spacing: Duration::days(2),
+            },
+            Recurrence::Period {
+                spacing: Duration::of(3, TimeUnit::Month),
+            },
+            Recurrence::Lead {
+                period: Duration::of(8, TimeUnit::Year),
+                earliest: Duration::of(6, TimeUnit::Month),
+                latest: Duration::of(3, TimeUnit::Month),
+            },
+            Recurrence::About {
+                spacing: Duration::of(1, TimeUnit::Year),
+                early: Duration::days(30),
+                late: Duration::days(60),
+            },
+        ];
+        for sut in recurrences {
+            assert_eq!(Ok(sut), sut.to_string().parse());
+        }
+
+        assert!("3d".parse::<Recurrence>().is_err());
+        assert!("^{3}".parse::<Recurrence>().is_err());
+        assert!("^~1y[30d,60d]".parse::<Recurrence>().is_err());
     }
 
     #[test]
@@ -100,6 +264,32 @@ mod test {
             spacing: Duration::days(1),
         };
         assert_eq!(Some(7), sut.remaining());
+
+        let sut = Recurrence::Lead {
+            period: Duration::of(8, TimeUnit::Year),
+            earliest: Duration::of(6, TimeUnit::Month),
+            latest: Duration::of(3, TimeUnit::Month),
+        };
+        assert_eq!(None, sut.remaining());
+    }
+
+    #[test]
+    fn test_spacing() {
+        let sut = Recurrence::Once;
+        assert_eq!(None, sut.spacing());
+
+        let sut = Recurrence::Times {
+            count: 7,
+            spacing: Duration::days(3),
+        };
+        assert_eq!(Some(Duration::days(3)), sut.spacing());
+
+        let sut = Recurrence::Lead {
+            period: Duration::days(100),
+            earliest: Duration::days(20),
+            latest: Duration::days(5),
+        };
+        assert_eq!(Some(Duration::days(100)), sut.spacing());
     }
 
     #[test]
@@ -119,5 +309,60 @@ mod test {
             spacing: Duration::days(3),
         };
         assert_eq!(ts + TimeDelta::days(3), sut.spaced(ts));
+
+        let sut = Recurrence::Lead {
+            period: Duration::days(100),
+            earliest: Duration::days(20),
+            latest: Duration::days(5),
+        };
+        assert_eq!(ts + TimeDelta::days(80), sut.spaced(ts));
+
+        let sut = Recurrence::About {
+            spacing: Duration::days(100),
+            early: Duration::days(20),
+            late: Duration::days(5),
+        };
+        assert_eq!(ts + TimeDelta::days(80), sut.spaced(ts));
+    }
+
+    #[test]
+    fn test_limit() {
+        let ts = d(2026, 10, 23, 0, 0, 0);
+
+        let sut = Recurrence::Period {
+            spacing: Duration::days(1),
+        };
+        assert_eq!(None, sut.limit(ts));
+
+        let sut = Recurrence::Lead {
+            period: Duration::days(100),
+            earliest: Duration::days(20),
+            latest: Duration::days(5),
+        };
+        assert_eq!(Some(ts + TimeDelta::days(95)), sut.limit(ts));
+
+        let sut = Recurrence::About {
+            spacing: Duration::days(100),
+            early: Duration::days(20),
+            late: Duration::days(5),
+        };
+        assert_eq!(Some(ts + TimeDelta::days(105)), sut.limit(ts));
+    }
+
+    #[test]
+    fn test_nominal() {
+        let ts = d(2026, 10, 23, 0, 0, 0);
+
+        let sut = Recurrence::Period {
+            spacing: Duration::days(100),
+        };
+        assert_eq!(None, sut.nominal(ts));
+
+        let sut = Recurrence::About {
+            spacing: Duration::days(100),
+            early: Duration::days(20),
+            late: Duration::days(5),
+        };
+        assert_eq!(Some(ts + TimeDelta::days(100)), sut.nominal(ts));
     }
 }
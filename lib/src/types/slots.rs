@@ -7,6 +7,10 @@ mod week_slots;
 use chrono::DateTime;
 use chrono::TimeDelta;
 use chrono::TimeZone;
+use chrono::Timelike;
+
+use crate::types::DayOfWeek;
+use crate::types::ParseError;
 
 /// A time slot for scheduling affinity.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -26,6 +30,77 @@ impl Slot {
         }
     }
 
+    /// Returns if the whole span `[start, end)` matches the affinity
+    /// represented by the slot.
+    ///
+    /// Slots change on hour boundaries, so it suffices to check `start` and
+    /// every full hour within the span.
+    pub fn fits_chrono<T: TimeZone>(&self, start: DateTime<T>, end: DateTime<T>) -> bool {
+        let mut ts = start;
+        loop {
+            if !self.matches_chrono(ts.clone()) {
+                return false;
+            }
+            let elapsed = TimeDelta::minutes(ts.minute() as i64)
+                + TimeDelta::seconds(ts.second() as i64)
+                + TimeDelta::nanoseconds(ts.nanosecond() as i64);
+            ts = ts + TimeDelta::hours(1) - elapsed;
+            if ts >= end {
+                return true;
+            }
+        }
+    }
+
+    /// Returns the earliest timestamp at or after `ts` that matches the slot.
+    ///
+    /// When `ts` does not match, the result is snapped to the initial
+    /// boundary of the slot, e.g. the first hour of a day.
+    pub fn next_chrono<T: TimeZone>(&self, ts: DateTime<T>) -> DateTime<T> {
+        if self.matches_chrono(ts.clone()) {
+            return ts;
+        }
+
+        let delta = self.fwd_delta_chrono(ts.clone());
+        let floor = ts
+            .with_nanosecond(0)
+            .and_then(|ts| ts.with_second(0))
+            .and_then(|ts| ts.with_minute(0));
+        let floor = match self {
+            Slot::Hour(_) => floor,
+            Slot::Week(_) => floor.and_then(|ts| ts.with_hour(0)),
+        };
+        floor.unwrap_or(ts) + delta
+    }
+
+    /// Returns which hours of the week match the slot, starting from Monday
+    /// at midnight.
+    pub fn hours_of_week(&self) -> [bool; 168] {
+        let mut hours = [false; 168];
+        for (idx, matches) in hours.iter_mut().enumerate() {
+            let (day, hour) = (idx as u32 / 24, idx as u32 % 24);
+            *matches = match self {
+                Slot::Hour(slot) => slot.matches(hour),
+                Slot::Week(slot) => slot.matches(DayOfWeek::from(day)),
+            };
+        }
+        hours
+    }
+
+    /// Returns the end of the window of the slot that `ts` falls in, that
+    /// is, the first full hour after `ts` that does not match, or `None` if
+    /// the slot never closes.
+    pub fn window_end<T: TimeZone>(&self, ts: DateTime<T>) -> Option<DateTime<T>> {
+        let floor = ts
+            .clone()
+            .with_nanosecond(0)
+            .and_then(|ts| ts.with_second(0))
+            .and_then(|ts| ts.with_minute(0))
+            .unwrap_or(ts);
+        (1..=168)
+            .map(|hours| floor.clone() + TimeDelta::hours(hours))
+            .find(|end| !self.matches_chrono(end.clone()))
+    }
+
     /// Returns the [`TimeDelta`] that `ts` must advance to fit within the
     /// initial boundary of the slot.
     pub fn fwd_delta_chrono<T: TimeZone>(&self, ts: DateTime<T>) -> TimeDelta {
@@ -45,6 +120,20 @@ impl Slot {
     }
 }
 
+impl std::str::FromStr for Slot {
+    type Err = ParseError;
+
+    /// Parses either an [HourSlot] or a [WeekSlot], as displayed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with(|c: char| c.is_ascii_digit()) {
+            s.parse().map(Slot::Hour)
+        } else {
+            s.parse().map(Slot::Week)
+        }
+        .map_err(|_| ParseError::new("slot", s))
+    }
+}
+
 impl std::fmt::Display for Slot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -53,3 +142,114 @@ impl std::fmt::Display for Slot {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::test::d;
+
+    #[test]
+    fn test_fits_chrono() {
+        let sut = Slot::Hour(HourSlot::Range {
+            start: 10,
+            stop: 13,
+        });
+
+        assert!(sut.fits_chrono(d(2025, 10, 23, 10, 0, 0), d(2025, 10, 23, 13, 0, 0)));
+        assert!(sut.fits_chrono(d(2025, 10, 23, 11, 0, 0), d(2025, 10, 23, 14, 0, 0)));
+        assert!(sut.fits_chrono(d(2025, 10, 23, 13, 30, 0), d(2025, 10, 23, 13, 30, 0)));
+        assert!(!sut.fits_chrono(d(2025, 10, 23, 13, 0, 0), d(2025, 10, 23, 16, 0, 0)));
+        assert!(!sut.fits_chrono(d(2025, 10, 23, 9, 30, 0), d(2025, 10, 23, 11, 0, 0)));
+
+        let sut = Slot::Week(WeekSlot::weekend());
+        assert!(sut.fits_chrono(d(2025, 10, 25, 22, 0, 0), d(2025, 10, 26, 2, 0, 0)));
+        assert!(!sut.fits_chrono(d(2025, 10, 26, 22, 0, 0), d(2025, 10, 27, 2, 0, 0)));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(Ok(Slot::Hour(HourSlot::Fixed { hour: 9 })), "09:00".parse());
+        assert_eq!(
+            Ok(Slot::Hour(HourSlot::Range { start: 22, stop: 3 })),
+            "22:00-03:00".parse()
+        );
+        assert_eq!(Ok(Slot::Week(WeekSlot::workdays())), "Mon-Fri".parse());
+        assert_eq!(
+            Ok(Slot::Week(WeekSlot::Fixed {
+                day: DayOfWeek::Sat
+            })),
+            "Sat".parse()
+        );
+        assert_eq!(
+            Err(ParseError::new("slot", "25:00")),
+            "25:00".parse::<Slot>()
+        );
+        assert!("09:30".parse::<Slot>().is_err());
+        assert!("Mon-Funday".parse::<Slot>().is_err());
+    }
+
+    #[test]
+    fn test_window_end() {
+        let sut = Slot::Hour(HourSlot::Range {
+            start: 18,
+            stop: 21,
+        });
+        assert_eq!(
+            Some(d(2025, 10, 23, 22, 0, 0)),
+            sut.window_end(d(2025, 10, 23, 19, 30, 0))
+        );
+
+        let sut = Slot::Hour(HourSlot::Range { start: 22, stop: 3 });
+        assert_eq!(
+            Some(d(2025, 10, 24, 4, 0, 0)),
+            sut.window_end(d(2025, 10, 23, 23, 0, 0))
+        );
+
+        assert_eq!(
+            None,
+            Slot::Week(WeekSlot::full()).window_end(d(2025, 10, 23, 23, 0, 0))
+        );
+    }
+
+    #[test]
+    fn test_next_chrono() {
+        let sut = Slot::Hour(HourSlot::Range {
+            start: 10,
+            stop: 13,
+        });
+        assert_eq!(
+            d(2025, 10, 23, 11, 30, 0),
+            sut.next_chrono(d(2025, 10, 23, 11, 30, 0))
+        );
+        assert_eq!(
+            d(2025, 10, 23, 10, 0, 0),
+            sut.next_chrono(d(2025, 10, 23, 8, 30, 0))
+        );
+        assert_eq!(
+            d(2025, 10, 24, 10, 0, 0),
+            sut.next_chrono(d(2025, 10, 23, 14, 30, 0))
+        );
+
+        let sut = Slot::Hour(HourSlot::Range { start: 22, stop: 3 });
+        assert_eq!(
+            d(2025, 10, 23, 1, 0, 0),
+            sut.next_chrono(d(2025, 10, 23, 1, 0, 0))
+        );
+        assert_eq!(
+            d(2025, 10, 23, 22, 0, 0),
+            sut.next_chrono(d(2025, 10, 23, 4, 0, 0))
+        );
+
+        // Thursday.
+        let sut = Slot::Week(WeekSlot::weekend());
+        assert_eq!(
+            d(2025, 10, 25, 0, 0, 0),
+            sut.next_chrono(d(2025, 10, 23, 14, 30, 0))
+        );
+        assert_eq!(
+            d(2025, 10, 26, 14, 30, 0),
+            sut.next_chrono(d(2025, 10, 26, 14, 30, 0))
+        );
+    }
+}
@@ -3,6 +3,8 @@ use chrono::TimeDelta;
 use chrono::TimeZone;
 use chrono::Timelike;
 
+use crate::types::ParseError;
+
 /// Represents a specific hour or hour range in a day (0-23).
 ///
 /// Used to specify when a [`Blueprint`](crate::types::Blueprint) has
@@ -92,6 +94,28 @@ impl HourSlot {
     }
 }
 
+impl std::str::FromStr for HourSlot {
+    type Err = ParseError;
+
+    /// Parses a full hour, e.g. "09:00", or a range of them, e.g.
+    /// "09:00-17:00".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hour = |h: &str| {
+            h.strip_suffix(":00")
+                .and_then(|h| h.parse().ok())
+                .filter(|h| *h < 24)
+                .ok_or_else(|| ParseError::new("hour slot", s))
+        };
+        match s.split_once('-') {
+            Some((start, stop)) => Ok(HourSlot::Range {
+                start: hour(start)?,
+                stop: hour(stop)?,
+            }),
+            None => Ok(HourSlot::Fixed { hour: hour(s)? }),
+        }
+    }
+}
+
 impl std::fmt::Display for HourSlot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
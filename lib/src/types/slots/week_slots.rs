@@ -3,6 +3,7 @@ use chrono::Datelike;
 use chrono::TimeDelta;
 use chrono::TimeZone;
 
+use crate::types::ParseError;
 use crate::types::days::DayOfWeek;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -105,6 +106,23 @@ impl WeekSlot {
     }
 }
 
+impl std::str::FromStr for WeekSlot {
+    type Err = ParseError;
+
+    /// Parses a day of the week, e.g. "Sat", or a range of them, e.g.
+    /// "Mon-Fri".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = |d: &str| d.parse().map_err(|_| ParseError::new("week slot", s));
+        match s.split_once('-') {
+            Some((start, stop)) => Ok(WeekSlot::Range {
+                start: day(start)?,
+                stop: day(stop)?,
+            }),
+            None => Ok(WeekSlot::Fixed { day: day(s)? }),
+        }
+    }
+}
+
 impl std::fmt::Display for WeekSlot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
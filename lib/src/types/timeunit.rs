@@ -1,3 +1,5 @@
+use crate::types::ParseError;
+
 /// Models the unit of time.
 ///
 /// The natural ordering corresponds to duration magnitude:
@@ -42,6 +44,22 @@ impl TimeUnit {
     }
 }
 
+impl std::str::FromStr for TimeUnit {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "s" => Ok(TimeUnit::Second),
+            "min" => Ok(TimeUnit::Minute),
+            "h" => Ok(TimeUnit::Hour),
+            "d" => Ok(TimeUnit::Day),
+            "mo" => Ok(TimeUnit::Month),
+            "y" => Ok(TimeUnit::Year),
+            _ => Err(ParseError::new("time unit", s)),
+        }
+    }
+}
+
 impl std::fmt::Display for TimeUnit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.as_str())